@@ -51,3 +51,9 @@ impl CommandMap {
         self.commands.get(name)
     }
 }
+
+impl Default for CommandMap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
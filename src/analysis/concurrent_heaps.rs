@@ -0,0 +1,85 @@
+use std::hint::black_box;
+use std::sync::Mutex;
+use std::thread;
+
+use csv::Writer;
+use rand::Rng;
+
+use crate::ds::heap::binary_heap_vec::BinaryHeapVec;
+use crate::ds::heap::concurrent::MultiQueue;
+use crate::ds::heap::ConcurrentHeap;
+
+use crate::analysis::utils::{generate_random_vector, measure_execution};
+
+use super::commands::{CommandDescriptor, CommandMap};
+
+/// Number of elements in the queue before the threads start
+const PREFILL: usize = 100_000;
+
+/// Number of operations shared out between the threads, half pushes and half pops
+const OPERATIONS: usize = 2_000_000;
+
+/// Measure how long threads take to run a fixed number of random pushes and pops in total.
+///
+/// Rows hold the thread count and the time taken, throughput being printed alongside.
+fn throughput_measurements<Q: ConcurrentHeap<i32>>(make: impl Fn(usize) -> Q, filename: &str) {
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for threads in [1, 2, 4, 8, 16] {
+        let queue = make(threads);
+        for element in generate_random_vector(PREFILL) {
+            queue.push(element);
+        }
+
+        let operations = OPERATIONS / threads;
+        let d = measure_execution(|| {
+            thread::scope(|scope| {
+                for _ in 0..threads {
+                    scope.spawn(|| {
+                        let mut rng = rand::thread_rng();
+                        for _ in 0..operations {
+                            if rng.gen_bool(0.5) {
+                                queue.push(rng.gen_range(0..100_000_000));
+                            } else {
+                                black_box(queue.pop());
+                            }
+                        }
+                    });
+                }
+            });
+        });
+
+        wtr.write_record(&[format!("{}", threads), format!("{}", d.as_secs_f64())])
+            .unwrap();
+        println!(
+            "{filename} threads={threads}: {:.0} operations per second",
+            (operations * threads) as f64 / d.as_secs_f64()
+        );
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// ENTRY POINTS
+
+fn analyze_heap_concurrent() {
+    println!(
+        "Running on {} available threads",
+        thread::available_parallelism().map_or(1, |threads| threads.get())
+    );
+
+    throughput_measurements(MultiQueue::with_threads, "data/multi_queue_concurrent.csv");
+    throughput_measurements(
+        |_| Mutex::new(BinaryHeapVec::new()),
+        "data/mutex_binary_heap_vec_concurrent.csv",
+    );
+}
+
+pub fn register_commands(cm: &mut CommandMap) {
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-concurrent",
+        "Run throughput analysis by thread count for concurrent priority queues",
+        analyze_heap_concurrent,
+    ));
+}
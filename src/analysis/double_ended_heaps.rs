@@ -0,0 +1,263 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hint::black_box;
+
+use csv::Writer;
+
+use crate::ds::heap::dual_heap::DualHeap;
+use crate::ds::heap::instrumented::{count_operations, Counted};
+use crate::ds::heap::interval_heap::IntervalHeap;
+use crate::ds::heap::min_max_heap::MinMaxHeap;
+use crate::ds::heap::DoubleEndedHeap;
+
+use crate::analysis::utils::{
+    generate_random_vector, measure_execution, measure_memory, measurement_record,
+};
+
+use super::commands::{CommandDescriptor, CommandMap};
+
+/// Number of best elements kept by the top-K workload
+const TOP_K: usize = 1_000;
+
+/// Number of most recent elements the sliding median is taken over
+const WINDOW: usize = 1_001;
+
+/// Half of a sliding median, with elements that left the window deleted lazily
+struct MedianHalf<T, H> {
+    heap: H,
+    pending: HashMap<T, usize>,
+    size: usize,
+    /// Whether this half gives out its largest element, as the lower half does
+    from_max: bool,
+}
+
+impl<T: Ord + Copy + Hash, H: DoubleEndedHeap<T> + Default> MedianHalf<T, H> {
+    fn new(from_max: bool) -> Self {
+        MedianHalf {
+            heap: H::default(),
+            pending: HashMap::new(),
+            size: 0,
+            from_max,
+        }
+    }
+
+    /// Discard deleted elements that reached either end of the heap
+    fn purge(&mut self) {
+        for use_max in [true, false] {
+            loop {
+                let end = if use_max {
+                    self.heap.peek_max()
+                } else {
+                    self.heap.peek_min()
+                };
+
+                match end.and_then(|value| self.pending.get_mut(value)) {
+                    Some(count) => {
+                        *count -= 1;
+                        if *count == 0 {
+                            let value = *end.unwrap();
+                            self.pending.remove(&value);
+                        }
+                    }
+                    None => break,
+                }
+
+                if use_max {
+                    self.heap.pop_max();
+                } else {
+                    self.heap.pop_min();
+                }
+            }
+        }
+    }
+
+    fn top(&mut self) -> Option<T> {
+        self.purge();
+
+        if self.from_max {
+            self.heap.peek_max().copied()
+        } else {
+            self.heap.peek_min().copied()
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        self.heap.push(value);
+        self.size += 1;
+    }
+
+    fn pop_top(&mut self) -> Option<T> {
+        self.purge();
+        self.size -= 1;
+
+        if self.from_max {
+            self.heap.pop_max()
+        } else {
+            self.heap.pop_min()
+        }
+    }
+
+    fn remove(&mut self, value: T) {
+        *self.pending.entry(value).or_insert(0) += 1;
+        self.size -= 1;
+    }
+}
+
+/// Median of a sliding window kept as a lower and an upper half
+struct SlidingMedian<T, H> {
+    lower: MedianHalf<T, H>,
+    upper: MedianHalf<T, H>,
+}
+
+impl<T: Ord + Copy + Hash, H: DoubleEndedHeap<T> + Default> SlidingMedian<T, H> {
+    fn new() -> Self {
+        SlidingMedian {
+            lower: MedianHalf::new(true),
+            upper: MedianHalf::new(false),
+        }
+    }
+
+    fn insert(&mut self, value: T) {
+        match self.lower.top() {
+            Some(top) if value > top => self.upper.push(value),
+            _ => self.lower.push(value),
+        }
+        self.rebalance();
+    }
+
+    fn remove(&mut self, value: T) {
+        match self.lower.top() {
+            Some(top) if value > top => self.upper.remove(value),
+            _ => self.lower.remove(value),
+        }
+        self.rebalance();
+    }
+
+    fn rebalance(&mut self) {
+        while self.lower.size > self.upper.size + 1 {
+            let value = self.lower.pop_top().unwrap();
+            self.upper.push(value);
+        }
+        while self.upper.size > self.lower.size {
+            let value = self.upper.pop_top().unwrap();
+            self.lower.push(value);
+        }
+    }
+
+    fn median(&mut self) -> Option<T> {
+        self.lower.top()
+    }
+}
+
+/// Keep the K largest elements of a stream, evicting the worst one and reading the best one
+fn top_k<T: Ord, H: DoubleEndedHeap<T>>(mut heap: H, elements: impl IntoIterator<Item = T>) {
+    for value in elements {
+        heap.push(value);
+        if heap.size() > TOP_K {
+            heap.pop_min();
+        }
+        black_box(heap.peek_max());
+    }
+}
+
+/// Compute the median of every window of consecutive elements of a stream
+fn sliding_median<T, H>(elements: &[T])
+where
+    T: Ord + Copy + Hash,
+    H: DoubleEndedHeap<T> + Default,
+{
+    let mut median = SlidingMedian::<T, H>::new();
+
+    for (index, &value) in elements.iter().enumerate() {
+        median.insert(value);
+        if index >= WINDOW {
+            median.remove(elements[index - WINDOW]);
+        }
+        black_box(median.median());
+    }
+}
+
+/// Measure a workload over n random elements, counting operations on a second run over
+/// counted elements.
+///
+/// Rows hold the fields of [`measurement_record`]. Counts cover the whole workload, which
+/// for the sliding median includes the lookups of lazily deleted elements.
+fn workload_measurements(
+    filename: &str,
+    run: impl Fn(&[i32]),
+    run_counted: impl Fn(&[Counted<i32>]),
+) {
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+        let counted: Vec<_> = vec.iter().copied().map(Counted).collect();
+
+        let (d, memory) = measure_memory(|| measure_execution(|| run(&vec)));
+        let ((), counts) = count_operations(|| run_counted(&counted));
+
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+/// Measure the top-K workload on heaps of type H, counting operations on heaps of type C
+fn top_k_measurements<H, C>(filename: &str)
+where
+    H: DoubleEndedHeap<i32> + Default,
+    C: DoubleEndedHeap<Counted<i32>> + Default,
+{
+    workload_measurements(
+        filename,
+        |elements| top_k(H::default(), elements.iter().copied()),
+        |elements| top_k(C::default(), elements.iter().copied()),
+    );
+}
+
+/// Measure the sliding median workload on heaps of type H, counting operations on heaps
+/// of type C
+fn sliding_median_measurements<H, C>(filename: &str)
+where
+    H: DoubleEndedHeap<i32> + Default,
+    C: DoubleEndedHeap<Counted<i32>> + Default,
+{
+    workload_measurements(
+        filename,
+        sliding_median::<i32, H>,
+        sliding_median::<Counted<i32>, C>,
+    );
+}
+
+// ENTRY POINTS
+
+fn analyze_heap_double_ended() {
+    top_k_measurements::<IntervalHeap<i32>, IntervalHeap<Counted<i32>>>(
+        "data/interval_heap_top_k.csv",
+    );
+    top_k_measurements::<MinMaxHeap<i32>, MinMaxHeap<Counted<i32>>>("data/min_max_heap_top_k.csv");
+    top_k_measurements::<DualHeap<i32>, DualHeap<Counted<i32>>>("data/dual_heap_top_k.csv");
+
+    sliding_median_measurements::<IntervalHeap<i32>, IntervalHeap<Counted<i32>>>(
+        "data/interval_heap_sliding_median.csv",
+    );
+    sliding_median_measurements::<MinMaxHeap<i32>, MinMaxHeap<Counted<i32>>>(
+        "data/min_max_heap_sliding_median.csv",
+    );
+    sliding_median_measurements::<DualHeap<i32>, DualHeap<Counted<i32>>>(
+        "data/dual_heap_sliding_median.csv",
+    );
+}
+
+pub fn register_commands(cm: &mut CommandMap) {
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-double-ended",
+        "Run top-K and sliding median analysis for double-ended heaps",
+        analyze_heap_double_ended,
+    ));
+}
@@ -0,0 +1,97 @@
+use std::hint::black_box;
+
+use csv::Writer;
+
+use crate::ds::heap::external::{ExternalHeap, Record};
+use crate::ds::heap::instrumented::{count_operations, Counted};
+
+use crate::analysis::utils::{
+    generate_random_vector, measure_execution, measure_memory, measurement_record,
+};
+
+use super::commands::{CommandDescriptor, CommandMap};
+
+/// Environment variable holding the memory budget in KiB, overriding the default sweep
+const MEMORY_BUDGET_VARIABLE: &str = "ALGODS_MEMORY_BUDGET";
+
+/// Budgets in KiB measured when no budget is given
+const DEFAULT_MEMORY_BUDGETS: [usize; 4] = [256, 1_024, 4_096, 16_384];
+
+/// Push all elements into an external heap, then pop them all again
+fn push_pop<T: Ord + Record + Copy>(heap: &mut ExternalHeap<T>, elements: &[T]) {
+    for &element in elements {
+        heap.push(element).unwrap();
+    }
+    while let Some(element) = heap.pop().unwrap() {
+        black_box(element);
+    }
+}
+
+/// Measure pushing n random elements into an external heap and popping them all again,
+/// counting operations on a second heap of counted elements.
+///
+/// Rows of data/external_heap_{budget}k.csv hold the fields of [`measurement_record`],
+/// rows of data/external_heap_{budget}k_io.csv hold n and the bytes written and read.
+fn external_heap_measurements(budget: usize) {
+    let filename = format!("data/external_heap_{budget}k.csv");
+    let io_filename = format!("data/external_heap_{budget}k_io.csv");
+
+    let mut wtr = Writer::from_path(&filename).unwrap();
+    let mut io_wtr = Writer::from_path(&io_filename).unwrap();
+
+    for n in [1_000_000, 2_000_000, 4_000_000, 8_000_000] {
+        let vec = generate_random_vector(n);
+        let mut heap = ExternalHeap::new(budget * 1_024);
+
+        let (d, memory) = measure_memory(|| measure_execution(|| push_pop(&mut heap, &vec)));
+
+        let counted: Vec<_> = vec.iter().copied().map(Counted).collect();
+        let mut counted_heap = ExternalHeap::new(budget * 1_024);
+        let ((), counts) = count_operations(|| push_pop(&mut counted_heap, &counted));
+
+        let stats = heap.stats();
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+        io_wtr
+            .write_record(&[
+                format!("{}", n),
+                format!("{}", stats.bytes_written),
+                format!("{}", stats.bytes_read),
+            ])
+            .unwrap();
+        println!(
+            "budget={budget}KiB n={n}: {:.2}s, {} runs, {:.1} bytes written per element",
+            d.as_secs_f64(),
+            stats.runs_written,
+            stats.bytes_written as f64 / n as f64
+        );
+    }
+
+    wtr.flush().unwrap();
+    io_wtr.flush().unwrap();
+    println!("Written {filename}");
+    println!("Written {io_filename}")
+}
+
+// ENTRY POINTS
+
+fn analyze_heap_external() {
+    let budgets = match std::env::var(MEMORY_BUDGET_VARIABLE) {
+        Ok(budget) => vec![budget
+            .parse()
+            .unwrap_or_else(|_| panic!("{MEMORY_BUDGET_VARIABLE} must be a number of KiB"))],
+        Err(_) => DEFAULT_MEMORY_BUDGETS.to_vec(),
+    };
+
+    for budget in budgets {
+        external_heap_measurements(budget);
+    }
+}
+
+pub fn register_commands(cm: &mut CommandMap) {
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-external",
+        "Run time and I/O volume analysis for the external heap, with the memory budget in KiB taken from ALGODS_MEMORY_BUDGET",
+        analyze_heap_external,
+    ));
+}
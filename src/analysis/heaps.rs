@@ -1,14 +1,80 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
 use csv::Writer;
+use rand::Rng;
 
-use crate::ds::heap::binary_heap_vec::BinaryHeapVec;
-use crate::ds::heap::binomial_heap::BinomialHeap;
-use crate::ds::heap::leftist_heap::LeftistHeap;
-use crate::ds::heap::{self as heap, Heap};
+use crate::ds::heap::binary_heap_vec::{AddressableBinaryHeap, BinaryHeapVec};
+use crate::ds::heap::binomial_heap::{ArenaBinomialHeap, BinomialHeap};
+use crate::ds::heap::d_ary_heap_vec::DAryHeapVec;
+use crate::ds::heap::fibonacci_heap::FibonacciHeap;
+use crate::ds::heap::instrumented::{count_operations, Counted, InstrumentedHeap, OperationCounts};
+use crate::ds::heap::leftist_heap::{ArenaLeftistHeap, LeftistHeap};
+use crate::ds::heap::pairing_heap::{PairingHeap, PopStrategy};
+use crate::ds::heap::radix_heap::{RadixHeap, RadixKey};
+use crate::ds::heap::rank_pairing_heap::{RankPairingHeap, RankRule};
+use crate::ds::heap::skew_heap::SkewHeap;
+use crate::ds::heap::soft_heap::SoftHeap;
+use crate::ds::heap::weak_heap::{weak_heapsort, WeakHeap};
+use crate::ds::heap::{self as heap, AddressableHeap, Heap, MergeableHeap, MonotoneHeap};
 
-use crate::analysis::utils::{generate_random_vector, measure_execution};
+use crate::analysis::utils::{
+    generate_random_vector, measure_execution, measure_memory, measurement_record, memory_fields,
+    MemoryUsage,
+};
 
 use super::commands::{CommandDescriptor, CommandMap};
 
+// OPERATION COUNTS
+
+/// Push elements wrapped to count the comparisons and clones made on them
+fn push_counted<H: Heap<Counted<i32>>>(heap: &mut H, elements: &[i32]) {
+    for &element in elements {
+        heap.push(Counted(element));
+    }
+}
+
+/// Operations made pushing elements one by one into an empty heap
+fn push_counts<H: Heap<Counted<i32>>>(
+    heap: H,
+    elements: impl IntoIterator<Item = i32>,
+) -> OperationCounts {
+    let mut heap = InstrumentedHeap::new(heap);
+    for element in elements {
+        heap.push(Counted(element));
+    }
+    heap.stats().push.counts
+}
+
+/// Operations made popping all elements after pushing them into an empty heap
+fn pop_counts<H: Heap<Counted<i32>>>(heap: H, elements: &[i32]) -> OperationCounts {
+    let mut heap = InstrumentedHeap::new(heap);
+    push_counted(&mut heap, elements);
+    while heap.pop().is_some() {}
+    heap.stats().pop.counts
+}
+
+/// Memory allocated pushing elements one by one into an empty heap, if allocations are
+/// tracked
+fn push_memory<H: Heap<i32>>(
+    mut heap: H,
+    elements: impl IntoIterator<Item = i32>,
+) -> Option<MemoryUsage> {
+    if !cfg!(feature = "track-allocations") {
+        return None;
+    }
+
+    let ((), memory) = measure_memory(|| {
+        for element in elements {
+            heap.push(element);
+        }
+    });
+    memory
+}
+
 fn leftist_heap_measurements() {
     let filename = "data/leftist_heap_insert.csv";
 
@@ -17,13 +83,15 @@ fn leftist_heap_measurements() {
 
     for n in [
         10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
-        500_000,
+        500_000, 700_000, 1_000_000, 2_000_000, 4_000_000,
     ] {
         let d = measure_execution(|| {
             heap::insert_n_elements(&mut heap, n);
         });
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(LeftistHeap::<Counted<i32>>::new(), 1..n);
+        let memory = push_memory(LeftistHeap::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
             .unwrap();
     }
 
@@ -39,7 +107,7 @@ fn leftist_heap_measurements_random() {
 
     for n in [
         10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
-        500_000,
+        500_000, 700_000, 1_000_000, 2_000_000, 4_000_000,
     ] {
         let vec = generate_random_vector(n);
 
@@ -48,7 +116,9 @@ fn leftist_heap_measurements_random() {
         });
 
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(LeftistHeap::<Counted<i32>>::new(), vec.iter().copied());
+        let memory = push_memory(LeftistHeap::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
             .unwrap();
     }
 
@@ -69,7 +139,9 @@ fn binomial_heap_measurements() {
             heap::insert_n_elements(&mut heap, n);
         });
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(BinomialHeap::<Counted<i32>>::new(), 1..n);
+        let memory = push_memory(BinomialHeap::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
             .unwrap();
     }
 
@@ -93,7 +165,86 @@ fn binomial_heap_measurements_random() {
         });
 
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(BinomialHeap::<Counted<i32>>::new(), vec.iter().copied());
+        let memory = push_memory(BinomialHeap::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// D-ARY HEAP
+
+/// Measure pop-heavy workloads for a given arity: insert n random elements, then pop them all
+fn d_ary_heap_vec_measurements<const D: usize>() {
+    let filename = format!("data/d_ary_heap_vec_{D}_pop_random.csv");
+    let mut wtr = Writer::from_path(&filename).unwrap();
+    let mut heap = DAryHeapVec::<i32, D>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000, 700_000, 1_000_000, 2_000_000, 4_000_000,
+    ] {
+        let vec = generate_random_vector(n);
+        heap::insert_n_vector_elements(&mut heap, &vec);
+
+        let (d, memory) = measure_memory(|| measure_execution(|| while heap.pop().is_some() {}));
+
+        let counts = pop_counts(DAryHeapVec::<_, D>::new(), &vec);
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// FIBONACCI HEAP
+
+fn fibonacci_heap_measurements() {
+    let filename = "data/fibonacci_heap_insert.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = FibonacciHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let d = measure_execution(|| {
+            heap::insert_n_elements(&mut heap, n);
+        });
+        heap.clear();
+        let counts = push_counts(FibonacciHeap::new(), 1..n);
+        let memory = push_memory(FibonacciHeap::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+fn fibonacci_heap_measurements_random() {
+    let filename = "data/fibonacci_heap_insert_random.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = FibonacciHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+
+        let d = measure_execution(|| {
+            heap::insert_n_vector_elements(&mut heap, &vec);
+        });
+
+        heap.clear();
+        let counts = push_counts(FibonacciHeap::new(), vec.iter().copied());
+        let memory = push_memory(FibonacciHeap::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
             .unwrap();
     }
 
@@ -116,7 +267,9 @@ fn binary_heap_vec_measurements() {
             heap::insert_n_elements(&mut heap, n);
         });
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(BinaryHeapVec::new(), 1..n);
+        let memory = push_memory(BinaryHeapVec::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
             .unwrap();
     }
 
@@ -140,7 +293,936 @@ fn binary_heap_vec_measurements_random() {
         });
 
         heap.clear();
-        wtr.write_record(&[format!("{}", n), format!("{}", d.as_secs_f64())])
+        let counts = push_counts(BinaryHeapVec::new(), vec.iter().copied());
+        let memory = push_memory(BinaryHeapVec::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// PAIRING HEAP
+
+fn pairing_heap_measurements() {
+    let filename = "data/pairing_heap_insert.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = PairingHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let d = measure_execution(|| {
+            heap::insert_n_elements(&mut heap, n);
+        });
+        heap.clear();
+        let counts = push_counts(PairingHeap::new(), 1..n);
+        let memory = push_memory(PairingHeap::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+fn pairing_heap_measurements_random() {
+    let filename = "data/pairing_heap_insert_random.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = PairingHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+
+        let d = measure_execution(|| {
+            heap::insert_n_vector_elements(&mut heap, &vec);
+        });
+
+        heap.clear();
+        let counts = push_counts(PairingHeap::new(), vec.iter().copied());
+        let memory = push_memory(PairingHeap::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// SKEW HEAP
+
+fn skew_heap_measurements() {
+    let filename = "data/skew_heap_insert.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = SkewHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let d = measure_execution(|| {
+            heap::insert_n_elements(&mut heap, n);
+        });
+        heap.clear();
+        let counts = push_counts(SkewHeap::new(), 1..n);
+        let memory = push_memory(SkewHeap::<i32>::new(), 1..n);
+        wtr.write_record(measurement_record(n as usize, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+fn skew_heap_measurements_random() {
+    let filename = "data/skew_heap_insert_random.csv";
+    let mut wtr = Writer::from_path(filename).unwrap();
+    let mut heap = SkewHeap::<i32>::new();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+
+        let d = measure_execution(|| {
+            heap::insert_n_vector_elements(&mut heap, &vec);
+        });
+
+        heap.clear();
+        let counts = push_counts(SkewHeap::new(), vec.iter().copied());
+        let memory = push_memory(SkewHeap::<i32>::new(), vec.iter().copied());
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// RADIX HEAP
+
+/// Priority queue operation recorded from a run of Dijkstra's algorithm
+enum TraceOp {
+    Push(u64, u32),
+    Pop,
+}
+
+/// Run Dijkstra's algorithm with lazy deletion on a random graph with n nodes and
+/// eight outgoing edges per node, recording every queue operation
+fn dijkstra_trace(n: usize) -> Vec<TraceOp> {
+    let mut rng = rand::thread_rng();
+    let edges: Vec<Vec<(u32, u64)>> = (0..n)
+        .map(|_| {
+            (0..8)
+                .map(|_| (rng.gen_range(0..n) as u32, rng.gen_range(1..=1_000)))
+                .collect()
+        })
+        .collect();
+
+    let mut distances = vec![u64::MAX; n];
+    let mut queue = BinaryHeapVec::<(u64, u32)>::new();
+    let mut trace = vec![TraceOp::Push(0, 0)];
+
+    distances[0] = 0;
+    queue.push((0, 0));
+
+    while let Some((distance, node)) = queue.pop() {
+        trace.push(TraceOp::Pop);
+        if distance > distances[node as usize] {
+            continue;
+        }
+
+        for &(next, weight) in &edges[node as usize] {
+            let candidate = distance + weight;
+            if candidate < distances[next as usize] {
+                distances[next as usize] = candidate;
+                queue.push((candidate, next));
+                trace.push(TraceOp::Push(candidate, next));
+            }
+        }
+    }
+
+    trace
+}
+
+/// Replay a Dijkstra trace on a radix heap, with keys made by `key`
+fn replay_radix<K: RadixKey>(trace: &[TraceOp], key: impl Fn(u64) -> K) {
+    let mut radix_heap = RadixHeap::<K, u32>::new();
+    for op in trace.iter() {
+        match *op {
+            TraceOp::Push(distance, node) => radix_heap.push(key(distance), node),
+            TraceOp::Pop => {
+                black_box(radix_heap.pop());
+            }
+        }
+    }
+}
+
+/// Replay a Dijkstra trace on a binary heap of pairs, with elements made by `element`
+fn replay_binary<E: Ord>(trace: &[TraceOp], element: impl Fn(u64, u32) -> E) {
+    let mut binary_heap = BinaryHeapVec::<E>::new();
+    for op in trace.iter() {
+        match *op {
+            TraceOp::Push(distance, node) => binary_heap.push(element(distance, node)),
+            TraceOp::Pop => {
+                black_box(binary_heap.pop());
+            }
+        }
+    }
+}
+
+/// Replay Dijkstra traces on a radix heap and on a binary heap of pairs.
+///
+/// Operation counts are per node of the graph, radix heap counts covering only the
+/// comparisons made on keys.
+fn radix_heap_measurements_dijkstra() {
+    let radix_filename = "data/radix_heap_dijkstra.csv";
+    let binary_filename = "data/binary_heap_vec_dijkstra.csv";
+
+    let mut radix_wtr = Writer::from_path(radix_filename).unwrap();
+    let mut binary_wtr = Writer::from_path(binary_filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let trace = dijkstra_trace(n);
+
+        let (d, memory) =
+            measure_memory(|| measure_execution(|| replay_radix(&trace, |distance| distance)));
+        let ((), counts) = count_operations(|| replay_radix(&trace, Counted));
+        radix_wtr
+            .write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+
+        let (d, memory) = measure_memory(|| {
+            measure_execution(|| replay_binary(&trace, |distance, node| (distance, node)))
+        });
+        let ((), counts) =
+            count_operations(|| replay_binary(&trace, |distance, node| Counted((distance, node))));
+        binary_wtr
+            .write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    radix_wtr.flush().unwrap();
+    binary_wtr.flush().unwrap();
+    println!("Written {radix_filename}");
+    println!("Written {binary_filename}")
+}
+
+// DECREASE KEY
+
+/// Operation of a synthetic workload on elements numbered in insertion order
+enum WorkloadOp {
+    Insert(u64),
+    DecreaseKey(u32, u64),
+    Pop,
+}
+
+/// Kinds of operation timed by the decrease-key workload, in the order of their timings
+const WORKLOAD_OPERATIONS: [&str; 3] = ["insert", "decrease_key", "pop"];
+
+/// Insert n random keys, then run 4n operations of which three quarters decrease the key
+/// of a random element still in the heap, and finally pop everything.
+///
+/// A binary heap tracks which elements are still present while generating, so that every
+/// decrease-key of the trace targets a live element.
+fn decrease_key_workload(n: usize) -> Vec<WorkloadOp> {
+    let mut rng = rand::thread_rng();
+    let mut heap = AddressableBinaryHeap::<(u64, u32)>::new();
+    let mut handles = Vec::new();
+    // Live elements, and the position of every element in that list
+    let mut live: Vec<u32> = Vec::new();
+    let mut positions = Vec::new();
+    let mut trace = Vec::new();
+
+    for step in 0..5 * n {
+        let kind = if step < n { 0 } else { rng.gen_range(0..8) };
+
+        match kind {
+            0 => {
+                let id = handles.len() as u32;
+                let key = rng.gen_range(0..1_000_000_000);
+                handles.push(heap.insert((key, id)));
+                positions.push(live.len());
+                live.push(id);
+                trace.push(WorkloadOp::Insert(key));
+            }
+            1 => {
+                if let Some((_, id)) = heap.pop() {
+                    let position = positions[id as usize];
+                    live.swap_remove(position);
+                    if let Some(&moved) = live.get(position) {
+                        positions[moved as usize] = position;
+                    }
+                    trace.push(WorkloadOp::Pop);
+                }
+            }
+            _ => {
+                if live.is_empty() {
+                    continue;
+                }
+                let id = live[rng.gen_range(0..live.len())];
+                let handle = handles[id as usize];
+                let key = rng.gen_range(0..=heap.get(handle).unwrap().0);
+
+                heap.decrease_key(handle, (key, id));
+                trace.push(WorkloadOp::DecreaseKey(id, key));
+            }
+        }
+    }
+
+    trace.extend((0..heap.size()).map(|_| WorkloadOp::Pop));
+    trace
+}
+
+/// Replay a workload on a heap with elements made by `element`, timing every operation
+/// and counting the operations it makes, both summed by kind
+fn replay_decrease_key_workload<E, H: AddressableHeap<E>>(
+    mut heap: H,
+    trace: &[WorkloadOp],
+    element: impl Fn(u64, u32) -> E,
+) -> ([Duration; 3], [OperationCounts; 3]) {
+    let mut handles = Vec::new();
+    let mut totals = [Duration::ZERO; 3];
+    let mut counts = [OperationCounts::default(); 3];
+
+    for op in trace {
+        let before = OperationCounts::current();
+        let start = Instant::now();
+        let kind = match *op {
+            WorkloadOp::Insert(key) => {
+                handles.push(heap.insert(element(key, handles.len() as u32)));
+                0
+            }
+            WorkloadOp::DecreaseKey(id, key) => {
+                heap.decrease_key(handles[id as usize], element(key, id));
+                1
+            }
+            WorkloadOp::Pop => {
+                black_box(heap.pop());
+                2
+            }
+        };
+        totals[kind] += start.elapsed();
+        counts[kind] += OperationCounts::current() - before;
+    }
+
+    (totals, counts)
+}
+
+/// Time a workload on a heap `H` of plain elements, and count its operations on a heap
+/// `C` of counted ones
+fn decrease_key_results<H, C>(
+    heap: H,
+    counted: C,
+    trace: &[WorkloadOp],
+) -> ([Duration; 3], [OperationCounts; 3])
+where
+    H: AddressableHeap<(u64, u32)>,
+    C: AddressableHeap<Counted<(u64, u32)>>,
+{
+    let (totals, _) = replay_decrease_key_workload(heap, trace, |key, id| (key, id));
+    let (_, counts) = replay_decrease_key_workload(counted, trace, |key, id| Counted((key, id)));
+    (totals, counts)
+}
+
+/// Replay decrease-key-heavy workloads on addressable heaps.
+///
+/// Rows of data/{heap}_decrease_key_{operation}.csv hold n and the average time per
+/// operation of that kind in seconds, including the cost of reading the clock, then the
+/// comparisons and clones per operation of that kind. Memory is not measured per
+/// operation, so its fields stay empty.
+fn decrease_key_measurements() {
+    let names = [
+        "rank_pairing_heap_type_1",
+        "rank_pairing_heap_type_2",
+        "fibonacci_heap",
+        "binary_heap_vec",
+    ];
+    let filename =
+        |name: &str, operation: &str| format!("data/{name}_decrease_key_{operation}.csv");
+
+    let mut writers: Vec<Vec<Writer<File>>> = names
+        .iter()
+        .map(|name| {
+            WORKLOAD_OPERATIONS
+                .iter()
+                .map(|operation| Writer::from_path(filename(name, operation)).unwrap())
+                .collect()
+        })
+        .collect();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let trace = decrease_key_workload(n);
+
+        let mut counts = [0; 3];
+        for op in &trace {
+            counts[match op {
+                WorkloadOp::Insert(_) => 0,
+                WorkloadOp::DecreaseKey(..) => 1,
+                WorkloadOp::Pop => 2,
+            }] += 1;
+        }
+
+        let results = [
+            decrease_key_results(
+                RankPairingHeap::with_rule(RankRule::Type1),
+                RankPairingHeap::with_rule(RankRule::Type1),
+                &trace,
+            ),
+            decrease_key_results(
+                RankPairingHeap::with_rule(RankRule::Type2),
+                RankPairingHeap::with_rule(RankRule::Type2),
+                &trace,
+            ),
+            decrease_key_results(FibonacciHeap::new(), FibonacciHeap::new(), &trace),
+            decrease_key_results(
+                AddressableBinaryHeap::new(),
+                AddressableBinaryHeap::new(),
+                &trace,
+            ),
+        ];
+
+        for ((name, wtrs), (totals, operations)) in names.iter().zip(&mut writers).zip(results) {
+            let averages: Vec<f64> = totals
+                .iter()
+                .zip(counts)
+                .map(|(total, count)| total.as_secs_f64() / count as f64)
+                .collect();
+
+            for (((wtr, average), operations), count) in
+                wtrs.iter_mut().zip(&averages).zip(operations).zip(counts)
+            {
+                let mut record = vec![
+                    format!("{}", n),
+                    format!("{}", average),
+                    format!("{}", operations.comparisons as f64 / count as f64),
+                    format!("{}", operations.clones as f64 / count as f64),
+                ];
+                record.extend(memory_fields(count, None));
+                wtr.write_record(record).unwrap();
+            }
+
+            if n == 500_000 {
+                println!(
+                    "{name} n={n}: insert {:.0} ns, decrease-key {:.0} ns, pop {:.0} ns",
+                    averages[0] * 1e9,
+                    averages[1] * 1e9,
+                    averages[2] * 1e9
+                );
+            }
+        }
+    }
+
+    for (name, wtrs) in names.iter().zip(writers) {
+        for (mut wtr, operation) in wtrs.into_iter().zip(WORKLOAD_OPERATIONS) {
+            wtr.flush().unwrap();
+            println!("Written {}", filename(name, operation));
+        }
+    }
+}
+
+// BULK BUILD
+
+/// Measure building a heap from n random elements in bulk and by repeated pushes, counting
+/// operations on the same heap of counted elements `C`
+fn bulk_build_measurements<H, C>(name: &str)
+where
+    H: Heap<i32> + FromIterator<i32> + Default,
+    C: Heap<Counted<i32>> + FromIterator<Counted<i32>> + Default,
+{
+    let bulk_filename = format!("data/{name}_bulk_build.csv");
+    let push_filename = format!("data/{name}_push_build.csv");
+
+    let mut bulk_wtr = Writer::from_path(&bulk_filename).unwrap();
+    let mut push_wtr = Writer::from_path(&push_filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+
+        let input = vec.clone();
+        let mut heap = H::default();
+        let (d, memory) = measure_memory(|| {
+            measure_execution(|| {
+                heap = H::from_iter(input);
+            })
+        });
+        let counted: Vec<_> = vec.iter().copied().map(Counted).collect();
+        let (_, counts) = count_operations(|| C::from_iter(counted));
+        bulk_wtr
+            .write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+
+        // Start from a fresh heap so pushes do not reuse the bulk build's allocations
+        heap = H::default();
+        let (d, memory) = measure_memory(|| {
+            measure_execution(|| {
+                heap::insert_n_vector_elements(&mut heap, &vec);
+            })
+        });
+        let counts = push_counts(C::default(), vec.iter().copied());
+        push_wtr
+            .write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    bulk_wtr.flush().unwrap();
+    push_wtr.flush().unwrap();
+    println!("Written {bulk_filename}");
+    println!("Written {push_filename}")
+}
+
+// ARENA
+
+/// Time inserting the elements of a vector into a fresh heap and record the memory it
+/// takes, leaving out dropping it
+fn measure_insert<H: Heap<i32> + Default>(vec: &Vec<i32>) -> (Duration, Option<MemoryUsage>) {
+    let mut heap = H::default();
+    measure_memory(|| {
+        measure_execution(|| {
+            heap::insert_n_vector_elements(&mut heap, vec);
+        })
+    })
+}
+
+/// Compare a slab-backed heap `A` with its boxed counterpart `B` on ascending and random
+/// inserts, printing how many times slower the boxed heap is. Operations are counted on
+/// the same heaps of counted elements, `CA` and `CB`.
+fn arena_measurements<A, B, CA, CB>(name: &str)
+where
+    A: Heap<i32> + Default,
+    B: Heap<i32> + Default,
+    CA: Heap<Counted<i32>> + Default,
+    CB: Heap<Counted<i32>> + Default,
+{
+    for (workload, random) in [("insert", false), ("insert_random", true)] {
+        let arena_filename = format!("data/{name}_arena_{workload}.csv");
+        let boxed_filename = format!("data/{name}_boxed_{workload}.csv");
+
+        let mut arena_wtr = Writer::from_path(&arena_filename).unwrap();
+        let mut boxed_wtr = Writer::from_path(&boxed_filename).unwrap();
+
+        for n in [
+            10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+            500_000,
+        ] {
+            let vec = if random {
+                generate_random_vector(n)
+            } else {
+                (1..n as i32).collect()
+            };
+
+            let (arena_d, arena_memory) = measure_insert::<A>(&vec);
+            let (boxed_d, boxed_memory) = measure_insert::<B>(&vec);
+
+            let arena_counts = push_counts(CA::default(), vec.iter().copied());
+            let boxed_counts = push_counts(CB::default(), vec.iter().copied());
+            arena_wtr
+                .write_record(measurement_record(n, arena_d, arena_counts, arena_memory))
+                .unwrap();
+            boxed_wtr
+                .write_record(measurement_record(n, boxed_d, boxed_counts, boxed_memory))
+                .unwrap();
+
+            let (arena, boxed) = (arena_d.as_secs_f64(), boxed_d.as_secs_f64());
+            println!(
+                "{name} {workload} n={n}: arena {arena:.4}s, boxed {boxed:.4}s, boxed/arena {:.2}x",
+                boxed / arena
+            );
+            if let (Some(arena), Some(boxed)) = (arena_memory, boxed_memory) {
+                println!(
+                    "{name} {workload} n={n}: peak bytes per element arena {:.1}, boxed {:.1}",
+                    arena.peak_live_bytes as f64 / n as f64,
+                    boxed.peak_live_bytes as f64 / n as f64
+                );
+            }
+        }
+
+        arena_wtr.flush().unwrap();
+        boxed_wtr.flush().unwrap();
+        println!("Written {arena_filename}");
+        println!("Written {boxed_filename}")
+    }
+}
+
+// STRING PAYLOADS
+
+/// Length of the strings pushed by the payload benchmark
+const STRING_PAYLOAD_LENGTH: usize = 128;
+
+/// Random strings of a fixed length, ordered by a random numeric prefix
+fn generate_string_payloads(n: usize) -> Vec<String> {
+    generate_random_vector(n)
+        .into_iter()
+        .map(|key| format!("{key:0width$}", width = STRING_PAYLOAD_LENGTH))
+        .collect()
+}
+
+/// Binary heap sifting by cloning elements into place, as `BinaryHeapVec` did before it
+/// moved them through a hole. Kept as the baseline of the string payload benchmark.
+#[derive(Default)]
+struct CloneSiftHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord + Clone> CloneSiftHeap<T> {
+    fn sift_up(&mut self, index: usize) {
+        let mut child_index = index;
+        let child_value = self.data[index].clone();
+
+        while child_index > 0 {
+            let parent_index = (child_index - 1) / 2;
+            if child_value >= self.data[parent_index] {
+                break;
+            }
+
+            self.data[child_index] = self.data[parent_index].clone();
+            child_index = parent_index;
+        }
+
+        self.data[child_index] = child_value;
+    }
+
+    fn sift_down(&mut self, index: usize) {
+        let mut parent_index = index;
+        let count = self.data.len();
+        let parent_value = self.data[parent_index].clone();
+
+        while parent_index < count / 2 {
+            let mut child_index = 2 * parent_index + 1;
+            if child_index < count - 1 && self.data[child_index] > self.data[child_index + 1] {
+                child_index += 1;
+            }
+            if parent_value <= self.data[child_index] {
+                break;
+            }
+
+            self.data[parent_index] = self.data[child_index].clone();
+            parent_index = child_index;
+        }
+
+        self.data[parent_index] = parent_value;
+    }
+}
+
+impl<T: Ord + Clone> Heap<T> for CloneSiftHeap<T> {
+    fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.sift_up(self.data.len() - 1);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let last = self.data.len().checked_sub(1)?;
+        self.data.swap(0, last);
+        let popped = self.data.pop();
+        if !self.data.is_empty() {
+            self.sift_down(0);
+        }
+        popped
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+}
+
+/// Push strings into a heap and pop them all again
+fn push_pop_strings<S, H: Heap<S>>(mut heap: H, strings: Vec<S>) {
+    for string in strings {
+        heap.push(string);
+    }
+    while let Some(string) = heap.pop() {
+        black_box(string);
+    }
+}
+
+/// Measure pushing n heap-allocated strings and popping them all again, counting
+/// operations on the same heap of counted strings `C`
+fn string_payload_measurements<H, C>(filename: &str)
+where
+    H: Heap<String> + Default,
+    C: Heap<Counted<String>> + Default,
+{
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let strings = generate_string_payloads(n);
+        let counted = strings.iter().cloned().map(Counted).collect();
+
+        let (d, memory) =
+            measure_memory(|| measure_execution(|| push_pop_strings(H::default(), strings)));
+        let ((), counts) = count_operations(|| push_pop_strings(C::default(), counted));
+
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+/// Push strings into a standard library heap and pop them all again
+fn std_push_pop_strings<S: Ord>(strings: Vec<S>) {
+    let mut heap = BinaryHeap::new();
+    for string in strings {
+        heap.push(Reverse(string));
+    }
+    while let Some(Reverse(string)) = heap.pop() {
+        black_box(string);
+    }
+}
+
+/// Same workload on the standard library heap, which moves elements through a hole
+fn std_binary_heap_string_measurements(filename: &str) {
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let strings = generate_string_payloads(n);
+        let counted = strings.iter().cloned().map(Counted).collect();
+
+        let (d, memory) = measure_memory(|| measure_execution(|| std_push_pop_strings(strings)));
+        let ((), counts) = count_operations(|| std_push_pop_strings(counted));
+
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// SOFT HEAP
+
+/// Measure the fraction of corrupted elements a soft heap holds after inserting n random
+/// elements, and the largest fraction it holds while popping them all again, by error rate.
+///
+/// Both are bounded by the error rate, counted against the n insertions.
+fn soft_heap_corruption_measurements(n: usize) {
+    let inserted_filename = "data/soft_heap_corruption.csv";
+    let peak_filename = "data/soft_heap_corruption_peak.csv";
+
+    let mut inserted_wtr = Writer::from_path(inserted_filename).unwrap();
+    let mut peak_wtr = Writer::from_path(peak_filename).unwrap();
+
+    for epsilon in [0.001, 0.01, 0.02, 0.05, 0.1, 0.2, 0.3, 0.5] {
+        let mut heap = SoftHeap::new(epsilon);
+        for element in generate_random_vector(n) {
+            heap.push(element);
+        }
+
+        let inserted = heap.corrupted_count() as f64 / n as f64;
+
+        // Counting takes O(n), so it is sampled every 1% of the pops
+        let mut peak = inserted;
+        while heap.size() > 0 {
+            for _ in 0..n.div_ceil(100) {
+                heap.pop();
+            }
+            peak = peak.max(heap.corrupted_count() as f64 / n as f64);
+        }
+
+        inserted_wtr
+            .write_record(&[format!("{}", epsilon), format!("{}", inserted)])
+            .unwrap();
+        peak_wtr
+            .write_record(&[format!("{}", epsilon), format!("{}", peak)])
+            .unwrap();
+        println!("epsilon={epsilon}: {inserted:.4} corrupted after inserting, {peak:.4} at most while popping");
+    }
+
+    inserted_wtr.flush().unwrap();
+    peak_wtr.flush().unwrap();
+    println!("Written {inserted_filename}");
+    println!("Written {peak_filename}")
+}
+
+// WEAK HEAP
+
+/// Measure the time taken to sort n random elements, and to push them one by one then
+/// pop them all, counting operations on counted elements sorted by the same function and
+/// pushed into a heap of type C.
+///
+/// Rows of data/{name}_sort.csv and data/{name}_push_pop.csv hold the fields of
+/// [`measurement_record`].
+fn comparison_measurements<H, C>(
+    name: &str,
+    sort: fn(Vec<i32>) -> Vec<i32>,
+    sort_counted: fn(Vec<Counted<i32>>) -> Vec<Counted<i32>>,
+) where
+    H: Heap<i32> + Default,
+    C: Heap<Counted<i32>> + Default,
+{
+    let sort_filename = format!("data/{name}_sort.csv");
+    let push_pop_filename = format!("data/{name}_push_pop.csv");
+
+    let mut sort_wtr = Writer::from_path(&sort_filename).unwrap();
+    let mut push_pop_wtr = Writer::from_path(&push_pop_filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+        let counted: Vec<_> = vec.iter().copied().map(Counted).collect();
+
+        let input = vec.clone();
+        let (sort_d, sort_memory) = measure_memory(|| {
+            measure_execution(|| {
+                black_box(sort(input));
+            })
+        });
+        let (_, sort_counts) = count_operations(|| sort_counted(counted));
+        sort_wtr
+            .write_record(measurement_record(n, sort_d, sort_counts, sort_memory))
+            .unwrap();
+
+        let (push_pop_d, push_pop_memory) =
+            measure_memory(|| measure_execution(|| push_pop(H::default(), &vec)));
+        let push_pop_counts =
+            push_counts(C::default(), vec.iter().copied()) + pop_counts(C::default(), &vec);
+        push_pop_wtr
+            .write_record(measurement_record(
+                n,
+                push_pop_d,
+                push_pop_counts,
+                push_pop_memory,
+            ))
+            .unwrap();
+
+        if n == 500_000 {
+            let n_log_n = n as f64 * (n as f64).log2();
+            println!(
+                "{name} n={n}: sort {:.3} n log2 n comparisons, push and pop {:.3} n log2 n",
+                sort_counts.comparisons as f64 / n_log_n,
+                push_pop_counts.comparisons as f64 / n_log_n
+            );
+        }
+    }
+
+    sort_wtr.flush().unwrap();
+    push_pop_wtr.flush().unwrap();
+    println!("Written {sort_filename}");
+    println!("Written {push_pop_filename}");
+}
+
+/// Sort with weak heapsort
+fn weak_heap_sort<T: Ord>(mut vec: Vec<T>) -> Vec<T> {
+    weak_heapsort(&mut vec);
+    vec
+}
+
+/// Sort by building a binary heap in place and popping all its elements
+fn binary_heap_sort<T: Ord>(vec: Vec<T>) -> Vec<T> {
+    BinaryHeapVec::from_vec(vec).into_sorted_vec()
+}
+
+/// Push all elements one by one, then pop them all
+fn push_pop<H: Heap<i32>>(mut heap: H, elements: &[i32]) {
+    for &element in elements {
+        heap.push(element);
+    }
+    while let Some(element) = heap.pop() {
+        black_box(element);
+    }
+}
+
+// POP
+
+/// Measure how long it takes to pop all elements after inserting n random ones, counting
+/// operations on a second heap of counted elements made by `make_counted`
+fn pop_measurements_random<H: Heap<i32>, C: Heap<Counted<i32>>>(
+    heap: &mut H,
+    make_counted: impl Fn() -> C,
+    filename: &str,
+) {
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let vec = generate_random_vector(n);
+        heap::insert_n_vector_elements(heap, &vec);
+
+        let (d, memory) = measure_memory(|| measure_execution(|| while heap.pop().is_some() {}));
+
+        let counts = pop_counts(make_counted(), &vec);
+        wtr.write_record(measurement_record(n, d, counts, memory))
+            .unwrap();
+    }
+
+    wtr.flush().unwrap();
+    println!("Written {filename}")
+}
+
+// MELD
+
+/// Number of elements in the heap receiving the meld when melding a large heap into it
+const LARGE_OTHER_HEAP_SIZE: usize = 1_000;
+
+/// Measure how long it takes to meld a heap holding n random elements into one holding
+/// `heap_size` of them, or n if not given, counting operations on the same meld of heaps
+/// of counted elements
+fn meld_measurements<H, C>(filename: &str, heap_size: Option<usize>)
+where
+    H: MergeableHeap<i32> + Default,
+    C: MergeableHeap<Counted<i32>> + Default,
+{
+    let mut wtr = Writer::from_path(filename).unwrap();
+
+    for n in [
+        10_000, 50_000, 100_000, 150_000, 200_000, 250_000, 300_000, 350_000, 400_000, 450_000,
+        500_000,
+    ] {
+        let first = generate_random_vector(heap_size.unwrap_or(n));
+        let second = generate_random_vector(n);
+
+        let mut heap = H::default();
+        let mut other = H::default();
+        heap::insert_n_vector_elements(&mut heap, &first);
+        heap::insert_n_vector_elements(&mut other, &second);
+
+        let (d, memory) = measure_memory(|| {
+            measure_execution(|| {
+                heap.meld(other);
+            })
+        });
+
+        let mut heap = C::default();
+        let mut other = C::default();
+        push_counted(&mut heap, &first);
+        push_counted(&mut other, &second);
+        let ((), counts) = count_operations(|| heap.meld(other));
+
+        wtr.write_record(measurement_record(n, d, counts, memory))
             .unwrap();
     }
 
@@ -153,11 +1235,77 @@ fn binary_heap_vec_measurements_random() {
 fn analyze_heap_leftist() {
     leftist_heap_measurements();
     leftist_heap_measurements_random();
+    pop_measurements_random(
+        &mut LeftistHeap::<i32>::new(),
+        LeftistHeap::<Counted<i32>>::new,
+        "data/leftist_heap_pop_random.csv",
+    );
 }
 
 fn analyze_heap_binomial() {
     binomial_heap_measurements();
-    binomial_heap_measurements_random()
+    binomial_heap_measurements_random();
+    pop_measurements_random(
+        &mut BinomialHeap::<i32>::new(),
+        BinomialHeap::<Counted<i32>>::new,
+        "data/binomial_heap_pop_random.csv",
+    );
+}
+
+fn analyze_heap_pairing() {
+    pairing_heap_measurements();
+    pairing_heap_measurements_random();
+
+    for (strategy, filename) in [
+        (
+            PopStrategy::TwoPass,
+            "data/pairing_heap_two_pass_pop_random.csv",
+        ),
+        (
+            PopStrategy::Multipass,
+            "data/pairing_heap_multipass_pop_random.csv",
+        ),
+        (
+            PopStrategy::FrontToBack,
+            "data/pairing_heap_front_to_back_pop_random.csv",
+        ),
+    ] {
+        pop_measurements_random(
+            &mut PairingHeap::<i32>::with_strategy(strategy),
+            || PairingHeap::with_strategy(strategy),
+            filename,
+        );
+    }
+}
+
+fn analyze_heap_skew() {
+    skew_heap_measurements();
+    skew_heap_measurements_random();
+    pop_measurements_random(
+        &mut SkewHeap::<i32>::new(),
+        SkewHeap::new,
+        "data/skew_heap_pop_random.csv",
+    );
+
+    // Leftist heap numbers for comparison
+    analyze_heap_leftist();
+}
+
+fn analyze_heap_radix() {
+    radix_heap_measurements_dijkstra();
+}
+
+fn analyze_heap_d_ary() {
+    d_ary_heap_vec_measurements::<2>();
+    d_ary_heap_vec_measurements::<3>();
+    d_ary_heap_vec_measurements::<4>();
+    d_ary_heap_vec_measurements::<8>();
+    d_ary_heap_vec_measurements::<16>();
+}
+
+fn analyze_heap_fibonacci() {
+    fibonacci_heap_measurements();
+    fibonacci_heap_measurements_random();
 }
 
 fn analyze_heap_binary() {
@@ -165,6 +1313,98 @@ fn analyze_heap_binary() {
     binary_heap_vec_measurements_random();
 }
 
+fn analyze_heap_decrease_key() {
+    decrease_key_measurements();
+}
+
+fn analyze_heap_bulk() {
+    bulk_build_measurements::<BinaryHeapVec<i32>, BinaryHeapVec<Counted<i32>>>("binary_heap_vec");
+    bulk_build_measurements::<LeftistHeap<i32>, LeftistHeap<Counted<i32>>>("leftist_heap");
+    bulk_build_measurements::<BinomialHeap<i32>, BinomialHeap<Counted<i32>>>("binomial_heap");
+}
+
+fn analyze_heap_arena() {
+    arena_measurements::<
+        ArenaLeftistHeap<i32>,
+        LeftistHeap<i32>,
+        ArenaLeftistHeap<Counted<i32>>,
+        LeftistHeap<Counted<i32>>,
+    >("leftist_heap");
+    arena_measurements::<
+        ArenaBinomialHeap<i32>,
+        BinomialHeap<i32>,
+        ArenaBinomialHeap<Counted<i32>>,
+        BinomialHeap<Counted<i32>>,
+    >("binomial_heap");
+}
+
+fn analyze_heap_strings() {
+    string_payload_measurements::<CloneSiftHeap<String>, CloneSiftHeap<Counted<String>>>(
+        "data/clone_sift_heap_strings.csv",
+    );
+    string_payload_measurements::<BinaryHeapVec<String>, BinaryHeapVec<Counted<String>>>(
+        "data/binary_heap_vec_strings.csv",
+    );
+    string_payload_measurements::<DAryHeapVec<String, 4>, DAryHeapVec<Counted<String>, 4>>(
+        "data/d_ary_heap_vec_4_strings.csv",
+    );
+    string_payload_measurements::<BinomialHeap<String>, BinomialHeap<Counted<String>>>(
+        "data/binomial_heap_strings.csv",
+    );
+    std_binary_heap_string_measurements("data/std_binary_heap_strings.csv");
+}
+
+fn analyze_heap_soft() {
+    soft_heap_corruption_measurements(500_000);
+}
+
+fn analyze_heap_weak() {
+    comparison_measurements::<WeakHeap<i32>, WeakHeap<Counted<i32>>>(
+        "weak_heap",
+        weak_heap_sort,
+        weak_heap_sort,
+    );
+    comparison_measurements::<BinaryHeapVec<i32>, BinaryHeapVec<Counted<i32>>>(
+        "binary_heap_vec",
+        binary_heap_sort,
+        binary_heap_sort,
+    );
+}
+
+fn analyze_heap_meld() {
+    // Equal sizes first, then a large heap melded into a small one, which shows the cost
+    // of melds linear in the size of the other heap
+    for (suffix, heap_size) in [
+        ("meld", None),
+        ("meld_large_other", Some(LARGE_OTHER_HEAP_SIZE)),
+    ] {
+        meld_measurements::<LeftistHeap<i32>, LeftistHeap<Counted<i32>>>(
+            &format!("data/leftist_heap_{suffix}.csv"),
+            heap_size,
+        );
+        meld_measurements::<SkewHeap<i32>, SkewHeap<Counted<i32>>>(
+            &format!("data/skew_heap_{suffix}.csv"),
+            heap_size,
+        );
+        meld_measurements::<BinomialHeap<i32>, BinomialHeap<Counted<i32>>>(
+            &format!("data/binomial_heap_{suffix}.csv"),
+            heap_size,
+        );
+        meld_measurements::<FibonacciHeap<i32>, FibonacciHeap<Counted<i32>>>(
+            &format!("data/fibonacci_heap_{suffix}.csv"),
+            heap_size,
+        );
+        meld_measurements::<RankPairingHeap<i32>, RankPairingHeap<Counted<i32>>>(
+            &format!("data/rank_pairing_heap_{suffix}.csv"),
+            heap_size,
+        );
+        meld_measurements::<BinaryHeapVec<i32>, BinaryHeapVec<Counted<i32>>>(
+            &format!("data/binary_heap_vec_{suffix}.csv"),
+            heap_size,
+        );
+    }
+}
+
 pub fn register_commands(cm: &mut CommandMap) {
     cm.add(CommandDescriptor::new(
         "analyze-heap-leftist",
@@ -181,4 +1421,64 @@ pub fn register_commands(cm: &mut CommandMap) {
         "Run runtime analysis for binary heap",
         analyze_heap_binary,
     ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-d-ary",
+        "Run pop runtime analysis for d-ary heaps of various arities",
+        analyze_heap_d_ary,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-fibonacci",
+        "Run runtime analysis for fibonacci heap",
+        analyze_heap_fibonacci,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-pairing",
+        "Run runtime analysis for pairing heap",
+        analyze_heap_pairing,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-skew",
+        "Run runtime analysis for skew heap next to leftist heap",
+        analyze_heap_skew,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-radix",
+        "Run radix heap and binary heap analysis on Dijkstra traces",
+        analyze_heap_radix,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-decrease-key",
+        "Run decrease-key-heavy workloads on addressable heaps, timing each operation type",
+        analyze_heap_decrease_key,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-bulk",
+        "Run bulk construction analysis against repeated pushes",
+        analyze_heap_bulk,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-arena",
+        "Run insert analysis for slab-backed heaps against boxed nodes",
+        analyze_heap_arena,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-strings",
+        "Run push and pop analysis with String elements, against a binary heap cloning them while sifting",
+        analyze_heap_strings,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-soft",
+        "Run corruption analysis for soft heaps by error rate",
+        analyze_heap_soft,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-weak",
+        "Run comparison count and runtime analysis for weak heap against binary heap",
+        analyze_heap_weak,
+    ));
+    cm.add(CommandDescriptor::new(
+        "analyze-heap-meld",
+        "Run meld cost analysis for mergeable heaps",
+        analyze_heap_meld,
+    ));
 }
@@ -1,3 +1,8 @@
+#[cfg(feature = "track-allocations")]
+pub mod allocator;
 pub mod commands;
+pub mod concurrent_heaps;
+pub mod double_ended_heaps;
+pub mod external_heaps;
 pub mod heaps;
 pub mod utils;
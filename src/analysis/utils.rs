@@ -1,6 +1,8 @@
 use rand::Rng;
 use std::time::{Duration, Instant};
 
+use crate::ds::heap::instrumented::OperationCounts;
+
 pub fn measure_execution<F>(f: F) -> Duration
 where
     F: FnOnce(),
@@ -10,6 +12,77 @@ where
     start.elapsed()
 }
 
+/// Memory allocated while running a function
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Bytes allocated in total, including those freed again
+    pub bytes_allocated: u64,
+    /// Highest number of bytes live at once, above those live before the function ran
+    pub peak_live_bytes: u64,
+    pub allocations: u64,
+}
+
+/// Run a function and record its allocations, which requires the tracking allocator of
+/// the `track-allocations` feature. Without it, no usage is returned.
+pub fn measure_memory<R, F>(f: F) -> (R, Option<MemoryUsage>)
+where
+    F: FnOnce() -> R,
+{
+    #[cfg(feature = "track-allocations")]
+    {
+        use super::allocator;
+
+        let start = allocator::reset_peak();
+        let result = f();
+        let end = allocator::snapshot();
+
+        // Resetting the peak can overwrite the raise of an allocation on another thread,
+        // leaving the peak below the live bytes
+        let usage = MemoryUsage {
+            bytes_allocated: end.allocated - start.allocated,
+            peak_live_bytes: end.peak.saturating_sub(start.live),
+            allocations: end.allocations - start.allocations,
+        };
+        (result, Some(usage))
+    }
+
+    #[cfg(not(feature = "track-allocations"))]
+    {
+        (f(), None)
+    }
+}
+
+/// Fields of the bytes allocated, peak live bytes and allocations per element, left
+/// empty when allocations are not tracked
+pub fn memory_fields(n: usize, memory: Option<MemoryUsage>) -> [String; 3] {
+    match memory {
+        Some(memory) => [
+            format!("{}", memory.bytes_allocated as f64 / n as f64),
+            format!("{}", memory.peak_live_bytes as f64 / n as f64),
+            format!("{}", memory.allocations as f64 / n as f64),
+        ],
+        None => Default::default(),
+    }
+}
+
+/// Row of a measurement over n elements: n, the time taken in seconds, the comparisons
+/// and clones per element, which unlike times are exact, then the memory fields
+pub fn measurement_record(
+    n: usize,
+    d: Duration,
+    counts: OperationCounts,
+    memory: Option<MemoryUsage>,
+) -> Vec<String> {
+    let mut record = vec![
+        format!("{}", n),
+        format!("{}", d.as_secs_f64()),
+        format!("{}", counts.comparisons as f64 / n as f64),
+        format!("{}", counts.clones as f64 / n as f64),
+    ];
+    record.extend(memory_fields(n, memory));
+    record
+}
+
 pub fn generate_random_vector(n: usize) -> Vec<i32> {
     let mut rng = rand::thread_rng();
     let mut vec = Vec::new();
@@ -20,3 +93,30 @@ pub fn generate_random_vector(n: usize) -> Vec<i32> {
 
     vec
 }
+
+#[cfg(test)]
+mod tests {
+    use super::measure_memory;
+
+    #[test]
+    #[cfg(feature = "track-allocations")]
+    fn test_measure_memory() {
+        // Counters are shared with tests running on other threads, so they can only be
+        // checked from below
+        let (vec, memory) = measure_memory(|| vec![0u8; 1 << 20]);
+        let memory = memory.unwrap();
+
+        assert_eq!(vec.len(), 1 << 20);
+        assert!(memory.bytes_allocated >= 1 << 20);
+        assert!(memory.allocations >= 1);
+    }
+
+    #[test]
+    #[cfg(not(feature = "track-allocations"))]
+    fn test_measure_memory_untracked() {
+        let (vec, memory) = measure_memory(|| vec![0u8; 1 << 20]);
+
+        assert_eq!(vec.len(), 1 << 20);
+        assert_eq!(memory, None);
+    }
+}
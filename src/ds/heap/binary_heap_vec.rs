@@ -1,109 +1,18 @@
-use super::Heap;
+use super::compare::MinOrder;
+use super::d_ary_heap_vec::{AddressableDAryHeap, DAryHeapVec};
 
-#[derive(Default)]
-pub struct BinaryHeapVec<T: Ord + Clone> {
-    data: Vec<T>,
-}
-
-impl<T: Ord + Clone> BinaryHeapVec<T> {
-    pub fn new() -> Self {
-        BinaryHeapVec { data: vec![] }
-    }
-
-    /// Bubble up element at given index
-    fn sift_up(&mut self, index: usize) {
-        let mut child_index = index;
-        let child_value = self.data[index].clone();
-
-        // While we are not at the top
-        while child_index > 0 {
-            // Find index of my parent
-            let parent_index = (child_index - 1) / 2;
-
-            // If child is larger or equal to the parent, we stop
-            if child_value >= self.data[parent_index] {
-                break;
-            }
-
-            // Otherwise, we override data of child with the data of the parent
-            self.data[child_index] = self.data[parent_index].clone();
-            child_index = parent_index;
-        }
-
-        self.data[child_index] = child_value;
-    }
-
-    fn sift_down(&mut self, index: usize) {
-        let mut parent_index = index;
-        let count = self.data.len();
-        let parent_value = self.data[parent_index].clone();
-
-        while parent_index < count / 2 {
-            let mut child_index = 2 * parent_index + 1;
-
-            if child_index < count - 1 && self.data[child_index] > self.data[child_index + 1] {
-                child_index += 1;
-            }
-
-            if parent_value <= self.data[child_index] {
-                break;
-            }
-
-            self.data[parent_index] = self.data[child_index].clone();
-            parent_index = child_index;
-        }
-
-        self.data[parent_index] = parent_value;
-    }
-}
-
-impl<T: Ord + Clone> Heap<T> for BinaryHeapVec<T> {
-    fn push(&mut self, value: T) {
-        self.data.push(value);
-        self.sift_up(self.data.len() - 1);
-    }
-
-    fn pop(&mut self) -> Option<T> {
-        if self.data.is_empty() {
-            return None;
-        }
+/// Classic binary heap stored in a vector
+pub type BinaryHeapVec<T, C = MinOrder> = DAryHeapVec<T, 2, C>;
 
-        if self.data.len() == 1 {
-            let popped_value = self.data.pop();
-            return popped_value;
-        }
-
-        let count = self.data.len();
-        self.data.swap(0, count - 1);
-
-        let popped_value = self.data.pop();
-        self.sift_down(0);
-
-        popped_value
-    }
-
-    fn peek(&self) -> Option<&T> {
-        if self.data.is_empty() {
-            return None;
-        }
-
-        Some(&self.data[0])
-    }
-
-    fn size(&self) -> usize {
-        self.data.len()
-    }
-
-    fn clear(&mut self) {
-        self.data.clear()
-    }
-}
+/// Binary heap whose elements can be updated or removed through handles
+pub type AddressableBinaryHeap<T, C = MinOrder> = AddressableDAryHeap<T, 2, C>;
 
 #[cfg(test)]
 mod tests {
-    use crate::ds::heap::Heap;
+    use crate::ds::heap::compare::MaxOrder;
+    use crate::ds::heap::{AddressableHeap, Heap, MergeableHeap};
 
-    use super::BinaryHeapVec;
+    use super::{AddressableBinaryHeap, BinaryHeapVec};
 
     #[test]
     fn test_basic_heap() {
@@ -162,4 +71,124 @@ mod tests {
         assert_eq!(heap.peek(), None);
         assert_eq!(heap.pop(), None);
     }
+
+    #[test]
+    fn test_handles() {
+        let mut heap = AddressableBinaryHeap::<i32>::new();
+
+        let handles: Vec<_> = (0..20).map(|i| heap.insert(100 + i)).collect();
+        assert_eq!(heap.pop(), Some(100));
+
+        heap.decrease_key(handles[15], 1);
+        heap.decrease_key(handles[10], 2);
+        assert_eq!(heap.get(handles[10]), Some(&2));
+
+        assert_eq!(heap.delete(handles[12]), Some(112));
+        assert_eq!(heap.delete(handles[12]), None);
+        assert_eq!(heap.get(handles[0]), None);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.size(), 16);
+        for i in (101..120).filter(|i| ![110, 112, 115].contains(i)) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = AddressableBinaryHeap::<i32>::new();
+        let mut other = AddressableBinaryHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+
+        let handle = heap.insert(200);
+        heap.meld(other);
+        heap.decrease_key(handle, -1);
+
+        assert_eq!(heap.size(), 101);
+        assert_eq!(heap.pop(), Some(-1));
+        for i in 0..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = AddressableBinaryHeap::<i32, MaxOrder>::default();
+
+        for i in 0..100 {
+            heap.push(i);
+        }
+        let handle = heap.insert(-1);
+
+        // In a max-heap, decreasing a key moves it up towards the largest elements
+        heap.decrease_key(handle, 150);
+
+        assert_eq!(heap.pop(), Some(150));
+        for i in (0..100).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_closure_comparator() {
+        // Order by last digit, larger numbers first among equal digits
+        let mut heap = BinaryHeapVec::with_comparator(|a: &i32, b: &i32| {
+            (a % 10).cmp(&(b % 10)).then(b.cmp(a))
+        });
+
+        for i in [15, 21, 30, 11, 40] {
+            heap.push(i);
+        }
+
+        assert_eq!(heap.pop(), Some(40));
+        assert_eq!(heap.pop(), Some(30));
+        assert_eq!(heap.pop(), Some(21));
+        assert_eq!(heap.pop(), Some(11));
+        assert_eq!(heap.pop(), Some(15));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut heap = BinaryHeapVec::from_vec_with_comparator((0..100).collect(), MaxOrder);
+        heap.extend([42, 150]);
+
+        assert_eq!(heap.pop(), Some(150));
+        for i in (42..100).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), Some(42));
+
+        let heap: BinaryHeapVec<i32> = (0..100).rev().collect();
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.size(), 100);
+    }
+
+    #[test]
+    fn test_move_only_elements() {
+        // Neither Clone nor Copy, so elements can only be moved around the heap
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Token(u32);
+
+        let mut heap = AddressableBinaryHeap::new();
+        let handles: Vec<_> = (0..50).map(|i| heap.insert(Token(100 + i))).collect();
+        heap.decrease_key(handles[30], Token(7));
+        assert_eq!(heap.delete(handles[0]), Some(Token(100)));
+
+        assert_eq!(heap.pop(), Some(Token(7)));
+        for i in (101..150).filter(|&i| i != 130) {
+            assert_eq!(heap.pop(), Some(Token(i)));
+        }
+        assert_eq!(heap.pop(), None);
+    }
 }
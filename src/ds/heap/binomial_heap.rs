@@ -1,121 +1,598 @@
-use super::Heap;
+use super::compare::{Compare, MinOrder};
+use super::handle::{Handle, Slab};
+use super::invariants::{self, InvariantViolation};
+use super::storage::{Inline, NodeStorage};
+use super::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
 
-#[derive(Clone)]
-pub struct Node<T: Ord> {
+pub struct Node<T, S: NodeStorage<Node<T, S>>> {
     element: T,
-    children: Vec<Node<T>>,
+    children: Vec<S::Link>,
 }
 
-#[derive(Clone)]
-pub struct BinomialHeap<T: Ord + Clone> {
-    trees: Vec<Node<T>>,
+/// Binomial heap ordered by a comparator, whose nodes live in the storage `S`, each node
+/// owning its children by default
+pub struct BinomialHeap<T, C = MinOrder, S: NodeStorage<Node<T, S>> = Inline> {
+    trees: Vec<S::Link>,
+    nodes: S,
+    compare: C,
 }
 
-impl<T: Ord + Clone> Node<T> {
+/// Binomial heap whose elements can be updated or removed through handles
+pub type AddressableBinomialHeap<T, C = MinOrder> = BinomialHeap<T, C, HandleNodes<T>>;
+
+/// Binomial heap keeping its nodes in a slab, linked by index
+pub type ArenaBinomialHeap<T, C = MinOrder> = BinomialHeap<T, C, ArenaNodes<T>>;
+
+/// Storage of addressable binomial heaps, keeping nodes in a slab linked by index.
+///
+/// Decreasing a key swaps elements up the tree, so handles point into a separate
+/// table that tracks the node each element currently lives in.
+pub struct HandleNodes<T> {
+    slab: Slab<Node<T, HandleNodes<T>>>,
+    /// Parent of the node in every slot of `slab`
+    parents: Vec<Option<usize>>,
+    /// Handle slot tracking the element of every node, if it was inserted with a handle
+    slots: Vec<Option<usize>>,
+    handles: Slab<usize>,
+}
+
+impl<T, S: NodeStorage<Node<T, S>>> Node<T, S> {
     fn new(element: T) -> Self {
         Node {
             element,
             children: Vec::new(),
         }
     }
+}
 
-    fn add_child(&mut self, child: Node<T>) {
-        self.children.push(child);
+impl<T> HandleNodes<T> {
+    /// Node holding the element behind a handle, if it is still in the heap
+    fn resolve(&self, handle: Handle) -> Option<usize> {
+        self.handles.resolve(handle).map(|slot| self.handles[slot])
     }
 
-    fn merge(node1: Node<T>, node2: Node<T>) -> Node<T> {
-        if node1.element < node2.element {
-            let mut node = node1.clone();
-            node.add_child(node2);
-            node
-        } else {
-            let mut node = node2.clone();
-            node.add_child(node1);
-            node
+    /// Swap the elements of a node and its parent, keeping handles pointed at their elements
+    fn swap_with_parent(&mut self, node: usize, parent: usize) {
+        let (child_node, parent_node) = self.slab.pair_mut(node, parent);
+        std::mem::swap(&mut child_node.element, &mut parent_node.element);
+        self.slots.swap(node, parent);
+
+        for index in [node, parent] {
+            if let Some(slot) = self.slots[index] {
+                self.handles[slot] = index;
+            }
         }
     }
 }
 
-impl<T: Ord + Clone> BinomialHeap<T> {
-    pub fn new() -> Self {
-        BinomialHeap { trees: Vec::new() }
+impl<T> Default for HandleNodes<T> {
+    fn default() -> Self {
+        HandleNodes {
+            slab: Slab::new(),
+            parents: Vec::new(),
+            slots: Vec::new(),
+            handles: Slab::new(),
+        }
     }
+}
 
-    pub fn merge_heap(&mut self, other: BinomialHeap<T>) {
-        self.trees.append(&mut other.trees.clone());
-        self.trees
-            .sort_by(|a, b| a.children.len().cmp(&b.children.len()));
-        let mut i = 0;
-        while i + 1 < self.trees.len() {
-            if self.trees[i].children.len() == self.trees[i + 1].children.len() {
-                let node = Node::merge(self.trees.remove(i), self.trees.remove(i));
-                self.trees.insert(i, node);
-            } else {
-                i += 1;
+impl<T> NodeStorage<Node<T, HandleNodes<T>>> for HandleNodes<T> {
+    type Link = usize;
+
+    fn alloc(&mut self, node: Node<T, Self>) -> usize {
+        let index = self.slab.insert(node).index();
+        self.parents.resize(self.slab.slots(), None);
+        self.slots.resize(self.slab.slots(), None);
+        self.parents[index] = None;
+        index
+    }
+
+    /// Frees the node along with the handle tracking its element
+    fn free(&mut self, link: usize) -> Node<T, Self> {
+        if let Some(slot) = self.slots[link].take() {
+            self.handles.remove(slot);
+        }
+        self.slab.remove(link)
+    }
+
+    fn node<'a>(&'a self, link: &'a usize) -> &'a Node<T, Self> {
+        &self.slab[*link]
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut usize) -> &'a mut Node<T, Self> {
+        &mut self.slab[*link]
+    }
+
+    /// Handles of `other` are consumed along with it
+    fn append(&mut self, other: Self, roots: &mut [usize]) {
+        let offset = self.slab.slots();
+
+        self.slab.append(other.slab, |node| {
+            for child in node.children.iter_mut() {
+                *child += offset;
             }
+        });
+        self.parents.extend(
+            other
+                .parents
+                .into_iter()
+                .map(|parent| parent.map(|index| index + offset)),
+        );
+        self.slots.resize(self.slab.slots(), None);
+
+        for root in roots {
+            *root += offset;
         }
     }
+
+    fn set_parent(&mut self, child: &usize, parent: Option<&usize>) {
+        self.parents[*child] = parent.copied();
+    }
+
+    /// Checks the parent link of the node and the handle slot tracking its element
+    fn check_node(
+        &self,
+        link: &usize,
+        parent: Option<&usize>,
+        position: usize,
+    ) -> Result<(), InvariantViolation> {
+        if self.parents[*link] != parent.copied() {
+            return Err(InvariantViolation::ParentLink { node: position });
+        }
+        if let Some(slot) = self.slots[*link] {
+            if self.handles.get(slot) != Some(link) {
+                return Err(InvariantViolation::Handle { slot });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn check_count(&self, reachable: usize) -> Result<(), InvariantViolation> {
+        if reachable != self.slab.len() {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected: self.slab.len(),
+            });
+        }
+
+        let tracked = self.slots.iter().flatten().count();
+        if tracked != self.handles.len() {
+            return Err(InvariantViolation::Size {
+                reachable: tracked,
+                expected: self.handles.len(),
+            });
+        }
+
+        Ok(())
+    }
 }
 
-impl<T: Ord + Clone> Heap<T> for BinomialHeap<T> {
-    fn push(&mut self, element: T) {
-        let node = Node::new(element);
-        self.merge_heap(BinomialHeap { trees: vec![node] });
+/// Storage keeping nodes in a slab linked by index, reusing freed slots instead of
+/// allocating a vector of children per node
+pub struct ArenaNodes<T> {
+    slab: Slab<Node<T, ArenaNodes<T>>>,
+}
+
+impl<T> Default for ArenaNodes<T> {
+    fn default() -> Self {
+        ArenaNodes { slab: Slab::new() }
     }
+}
 
-    fn peek(&self) -> Option<&T> {
-        self.trees
-            .iter()
-            .min_by(|x, y| x.element.cmp(&y.element))
-            .map(|node| &node.element)
+impl<T> NodeStorage<Node<T, ArenaNodes<T>>> for ArenaNodes<T> {
+    type Link = usize;
+
+    fn alloc(&mut self, node: Node<T, Self>) -> usize {
+        self.slab.insert(node).index()
     }
 
-    fn pop(&mut self) -> Option<T> {
-        if self.trees.is_empty() {
-            return None;
+    fn free(&mut self, link: usize) -> Node<T, Self> {
+        self.slab.remove(link)
+    }
+
+    fn node<'a>(&'a self, link: &'a usize) -> &'a Node<T, Self> {
+        &self.slab[*link]
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut usize) -> &'a mut Node<T, Self> {
+        &mut self.slab[*link]
+    }
+
+    fn append(&mut self, other: Self, roots: &mut [usize]) {
+        let offset = self.slab.slots();
+
+        self.slab.append(other.slab, |node| {
+            for child in node.children.iter_mut() {
+                *child += offset;
+            }
+        });
+
+        for root in roots {
+            *root += offset;
         }
+    }
 
-        // Find the tree with the minimum root
-        let min_index = self
-            .trees
+    fn check_count(&self, reachable: usize) -> Result<(), InvariantViolation> {
+        if reachable != self.slab.len() {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected: self.slab.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Ord, S: NodeStorage<Node<T, S>>> BinomialHeap<T, MinOrder, S> {
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+
+    /// Build a heap from a vector in O(n) by counting trees up like a binary counter
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(vec, MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> BinomialHeap<T, C, S> {
+    pub fn with_comparator(compare: C) -> Self {
+        BinomialHeap {
+            trees: Vec::new(),
+            nodes: S::default(),
+            compare,
+        }
+    }
+
+    pub fn from_vec_with_comparator(vec: Vec<T>, compare: C) -> Self {
+        let mut heap = Self::with_comparator(compare);
+        let nodes = heap.insert_nodes(vec);
+        heap.trees = heap.count_trees(nodes);
+        invariants::debug_check(&heap);
+        heap
+    }
+
+    /// Add elements as detached single-node trees
+    fn insert_nodes<I: IntoIterator<Item = T>>(&mut self, elements: I) -> Vec<S::Link> {
+        elements
+            .into_iter()
+            .map(|element| self.nodes.alloc(Node::new(element)))
+            .collect()
+    }
+
+    /// Combine single-node trees as increments of a binary counter, where a carry links
+    /// two trees of the same order. Each link is paid for by the increment that
+    /// created its carry, so k nodes are combined in O(k).
+    fn count_trees(&mut self, nodes: Vec<S::Link>) -> Vec<S::Link> {
+        let mut counter: Vec<Option<S::Link>> = Vec::new();
+
+        for node in nodes {
+            let mut carry = node;
+            let mut order = 0;
+
+            loop {
+                if order == counter.len() {
+                    counter.push(None);
+                }
+
+                match counter[order].take() {
+                    None => {
+                        counter[order] = Some(carry);
+                        break;
+                    }
+                    Some(tree) => {
+                        carry = self.link(tree, carry);
+                        order += 1;
+                    }
+                }
+            }
+        }
+
+        counter.into_iter().flatten().collect()
+    }
+
+    /// Link two trees of the same order, the one with the larger root becoming a child
+    fn link(&mut self, node1: S::Link, node2: S::Link) -> S::Link {
+        let (mut parent, child) = if self.compare.lt(
+            &self.nodes.node(&node1).element,
+            &self.nodes.node(&node2).element,
+        ) {
+            (node1, node2)
+        } else {
+            (node2, node1)
+        };
+
+        self.nodes.set_parent(&child, Some(&parent));
+        self.nodes.node_mut(&mut parent).children.push(child);
+        parent
+    }
+
+    fn order(&self, tree: &S::Link) -> usize {
+        self.nodes.node(tree).children.len()
+    }
+
+    /// Add two lists of trees like binary numbers, both listed by strictly increasing
+    /// order, so that the result holds at most one tree of each order
+    fn merge_trees(&mut self, other: Vec<S::Link>) {
+        let mut first = std::mem::take(&mut self.trees).into_iter().peekable();
+        let mut second = other.into_iter().peekable();
+        let mut carry: Option<S::Link> = None;
+        let mut trees = Vec::new();
+
+        while let Some(order) = [first.peek(), second.peek(), carry.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|tree| self.order(tree))
+            .min()
+        {
+            // Collect the up to three trees of the lowest order
+            let mut same = [
+                carry.take_if(|tree| self.order(tree) == order),
+                first.next_if(|tree| self.order(tree) == order),
+                second.next_if(|tree| self.order(tree) == order),
+            ]
+            .into_iter()
+            .flatten();
+
+            let tree = same.next().unwrap();
+            match (same.next(), same.next()) {
+                (None, _) => trees.push(tree),
+                (Some(tree2), None) => carry = Some(self.link(tree, tree2)),
+                (Some(tree1), Some(tree2)) => {
+                    trees.push(tree);
+                    carry = Some(self.link(tree1, tree2));
+                }
+            }
+        }
+
+        self.trees = trees;
+
+        // Every structural change ends with a merge, so this is where to look for damage
+        invariants::debug_check(self);
+    }
+
+    /// Position in `trees` of the tree whose root comes first
+    fn first_tree(&self) -> Option<usize> {
+        self.trees
             .iter()
             .enumerate()
-            .min_by(|(_, x), (_, y)| x.element.cmp(&y.element))
-            .map(|(index, _)| index)?;
+            .min_by(|(_, x), (_, y)| {
+                self.compare
+                    .compare(&self.nodes.node(x).element, &self.nodes.node(y).element)
+            })
+            .map(|(index, _)| index)
+    }
 
-        // Remove the minimum root
-        let Node {
-            element,
-            mut children,
-        } = self.trees.remove(min_index);
+    /// Free all nodes, collecting their elements in arbitrary order
+    fn take_elements(&mut self) -> Vec<T> {
+        let mut elements = Vec::new();
+        let mut stack = std::mem::take(&mut self.trees);
+
+        while let Some(node) = stack.pop() {
+            let node = self.nodes.free(node);
+            stack.extend(node.children);
+            elements.push(node.element);
+        }
+
+        elements
+    }
+
+    /// Remove the root of the tree at a given position, making its children new trees
+    fn remove_tree(&mut self, position: usize) -> T {
+        let root = self.trees.remove(position);
+        let Node { element, children } = self.nodes.free(root);
+
+        // Make each child a new tree, children being linked in by increasing order
+        for child in children.iter() {
+            self.nodes.set_parent(child, None);
+        }
+
+        // Merge the original heap with the new trees
+        self.merge_trees(children);
 
-        // Make each child a new tree
-        children.reverse();
-        let other = BinomialHeap { trees: children };
+        element
+    }
+}
 
-        // Merge the original heap with the new heap
-        self.merge_heap(other);
+impl<T, C: Compare<T> + Default, S: NodeStorage<Node<T, S>>> Default for BinomialHeap<T, C, S> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
 
-        Some(element)
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> Heap<T> for BinomialHeap<T, C, S> {
+    fn push(&mut self, element: T) {
+        let node = self.nodes.alloc(Node::new(element));
+        self.merge_trees(vec![node]);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.first_tree()
+            .map(|position| &self.nodes.node(&self.trees[position]).element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let position = self.first_tree()?;
+        Some(self.remove_tree(position))
     }
 
     fn size(&self) -> usize {
         self.trees
             .iter()
-            .map(|t| 2_usize.pow(t.children.len() as u32))
+            .map(|tree| 2_usize.pow(self.order(tree) as u32))
             .sum()
     }
 
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        let mut stack: Vec<_> = self.trees.iter().collect();
+
+        Box::new(std::iter::from_fn(move || {
+            let node = self.nodes.node(stack.pop()?);
+            stack.extend(node.children.iter());
+            Some(&node.element)
+        }))
+    }
+
+    /// Frees the nodes one by one, so that storages can release their slots
     fn clear(&mut self) {
-        self.trees.clear()
+        self.take_elements();
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.take_elements().into_iter())
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> IntoIterator for BinomialHeap<T, C, S> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the trees
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.take_elements().into_iter()
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> MergeableHeap<T> for BinomialHeap<T, C, S> {
+    /// Trees are merged like a binary addition in O(log n), on top of moving the nodes
+    /// of `other` into this heap's storage. Handles of `other` are consumed along with it.
+    fn meld(&mut self, mut other: Self) {
+        let mut trees = std::mem::take(&mut other.trees);
+        self.nodes
+            .append(std::mem::take(&mut other.nodes), &mut trees);
+        self.merge_trees(trees);
+    }
+}
+
+impl<T, C: Compare<T> + Default, S: NodeStorage<Node<T, S>>> FromIterator<T>
+    for BinomialHeap<T, C, S>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec_with_comparator(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> Extend<T> for BinomialHeap<T, C, S> {
+    /// Counts the new elements up into trees in O(k) and merges those in with the existing ones
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let nodes = self.insert_nodes(iter);
+        let trees = self.count_trees(nodes);
+        self.merge_trees(trees);
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> HeapInvariants for BinomialHeap<T, C, S> {
+    /// Checks that roots have strictly increasing orders and that every tree is a
+    /// binomial tree in heap order, along with what the storage records about its nodes
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        for pair in self.trees.windows(2) {
+            if self.order(&pair[0]) >= self.order(&pair[1]) {
+                return Err(InvariantViolation::RootOrder {
+                    order: self.order(&pair[1]),
+                });
+            }
+        }
+
+        let mut reachable = 0;
+        let mut stack: Vec<_> = self.trees.iter().map(|tree| (tree, None)).collect();
+
+        while let Some((link, parent)) = stack.pop() {
+            let node = reachable;
+            reachable += 1;
+
+            self.nodes
+                .check_node(link, parent.map(|(parent_link, _)| parent_link), node)?;
+
+            let current = self.nodes.node(link);
+            if let Some((parent_link, parent)) = parent {
+                if self
+                    .compare
+                    .lt(&current.element, &self.nodes.node(parent_link).element)
+                {
+                    return Err(InvariantViolation::HeapOrder {
+                        parent,
+                        child: node,
+                    });
+                }
+            }
+
+            // The i-th child of a binomial tree is itself a binomial tree of order i
+            for (order, child) in current.children.iter().enumerate() {
+                if self.order(child) != order {
+                    return Err(InvariantViolation::TreeShape { node });
+                }
+                stack.push((child, Some((link, node))));
+            }
+        }
+
+        self.nodes.check_count(reachable)
+    }
+}
+
+impl<T, C: Compare<T>> AddressableHeap<T> for AddressableBinomialHeap<T, C> {
+    fn insert(&mut self, element: T) -> Handle {
+        let node = self.nodes.alloc(Node::new(element));
+        let handle = self.nodes.handles.insert(node);
+
+        self.nodes.slots[node] = Some(handle.index());
+        self.merge_trees(vec![node]);
+
+        handle
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes
+            .resolve(handle)
+            .map(|node| &self.nodes.slab[node].element)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) {
+        let mut node = self.nodes.resolve(handle).expect("Invalid heap handle");
+        assert!(
+            self.compare.le(&element, &self.nodes.slab[node].element),
+            "New key is greater than the current key"
+        );
+        self.nodes.slab[node].element = element;
+
+        while let Some(parent) = self.nodes.parents[node] {
+            if self.compare.le(
+                &self.nodes.slab[parent].element,
+                &self.nodes.slab[node].element,
+            ) {
+                break;
+            }
+            self.nodes.swap_with_parent(node, parent);
+            node = parent;
+        }
+
+        invariants::debug_check(self);
+    }
+
+    fn delete(&mut self, handle: Handle) -> Option<T> {
+        let mut node = self.nodes.resolve(handle)?;
+
+        // Bubble the element all the way up as if its key was minus infinity
+        while let Some(parent) = self.nodes.parents[node] {
+            self.nodes.swap_with_parent(node, parent);
+            node = parent;
+        }
+
+        let position = self.trees.iter().position(|&tree| tree == node)?;
+        Some(self.remove_tree(position))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ds::heap::Heap;
+    use crate::ds::heap::compare::MaxOrder;
+    use crate::ds::heap::invariants::InvariantViolation;
+    use crate::ds::heap::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
 
-    use super::BinomialHeap;
+    use super::{AddressableBinomialHeap, ArenaBinomialHeap, BinomialHeap};
 
     #[test]
     fn test_basic_heap() {
@@ -174,4 +651,167 @@ mod tests {
         assert_eq!(heap.peek(), None);
         assert_eq!(heap.pop(), None);
     }
+
+    #[test]
+    fn test_invariants() {
+        let mut heap = BinomialHeap::<i32>::new();
+
+        // Three trees of the same order meet while merging, which used to leave two
+        // trees of the next order behind
+        for i in 0..7 {
+            heap.push(i);
+        }
+        let mut other = BinomialHeap::<i32>::new();
+        for i in 7..14 {
+            other.push(i);
+        }
+        heap.meld(other);
+        assert_eq!(heap.check_invariants(), Ok(()));
+        assert_eq!(heap.trees.len(), 3);
+
+        heap.trees.swap(0, 1);
+        assert!(matches!(
+            heap.check_invariants(),
+            Err(InvariantViolation::RootOrder { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut heap = BinomialHeap::<i32>::from_vec((0..100).rev().collect());
+        heap.extend([-1, 150]);
+        heap.extend(100..150);
+
+        assert_eq!(heap.size(), 152);
+        for i in -1..=150 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+
+        let heap: BinomialHeap<i32, MaxOrder> = (0..100).collect();
+        assert_eq!(heap.peek(), Some(&99));
+    }
+
+    #[test]
+    fn test_arena() {
+        let mut heap = ArenaBinomialHeap::<i32>::from_vec((0..100).rev().collect());
+        let mut other = ArenaBinomialHeap::<i32>::new();
+
+        other.extend((100..200).rev());
+        heap.meld(other);
+        for i in 0..50 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+
+        // Freed slots are reused by later pushes
+        heap.extend(0..50);
+        assert_eq!(heap.nodes.slab.slots(), 200);
+        assert_eq!(heap.check_invariants(), Ok(()));
+        for i in 0..200 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut heap = AddressableBinomialHeap::<i32>::new();
+
+        let handles: Vec<_> = (0..20).map(|i| heap.insert(100 + i)).collect();
+        assert_eq!(heap.pop(), Some(100));
+
+        heap.decrease_key(handles[15], 1);
+        heap.decrease_key(handles[10], 2);
+        assert_eq!(heap.get(handles[10]), Some(&2));
+
+        assert_eq!(heap.delete(handles[12]), Some(112));
+        assert_eq!(heap.delete(handles[12]), None);
+        assert_eq!(heap.get(handles[0]), None);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.size(), 16);
+        for i in (101..120).filter(|i| ![110, 112, 115].contains(i)) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = AddressableBinomialHeap::<i32>::new();
+        let mut other = AddressableBinomialHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        other.insert(300);
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+
+        let handle = heap.insert(200);
+        heap.meld(other);
+        heap.decrease_key(handle, -1);
+
+        assert_eq!(heap.size(), 102);
+        assert_eq!(heap.pop(), Some(-1));
+        for i in 0..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), Some(300));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = AddressableBinomialHeap::<i32, MaxOrder>::default();
+
+        let handles: Vec<_> = (0..20).map(|i| heap.insert(i)).collect();
+        heap.decrease_key(handles[3], 50);
+        assert_eq!(heap.delete(handles[19]), Some(19));
+
+        assert_eq!(heap.peek(), Some(&50));
+        assert_eq!(heap.pop(), Some(50));
+        for i in (0..19).rev().filter(|&i| i != 3) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_closure_comparator() {
+        let mut heap: BinomialHeap<_, _> =
+            BinomialHeap::with_comparator(|a: &(u32, char), b: &(u32, char)| {
+                a.0.cmp(&b.0).then(b.1.cmp(&a.1))
+            });
+
+        for entry in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')] {
+            heap.push(entry);
+        }
+
+        assert_eq!(heap.pop(), Some((1, 'd')));
+        assert_eq!(heap.pop(), Some((1, 'b')));
+        assert_eq!(heap.pop(), Some((2, 'c')));
+        assert_eq!(heap.pop(), Some((2, 'a')));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_move_only_elements() {
+        // Neither Clone nor Copy, so elements can only be moved around the heap
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Token(u32);
+
+        let mut heap = AddressableBinomialHeap::new();
+        let handles: Vec<_> = (0..50).map(|i| heap.insert(Token(100 + i))).collect();
+        heap.decrease_key(handles[30], Token(7));
+        assert_eq!(heap.delete(handles[0]), Some(Token(100)));
+
+        assert_eq!(heap.pop(), Some(Token(7)));
+        for i in (101..150).filter(|&i| i != 130) {
+            assert_eq!(heap.pop(), Some(Token(i)));
+        }
+        assert_eq!(heap.pop(), None);
+    }
 }
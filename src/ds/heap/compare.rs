@@ -0,0 +1,76 @@
+use std::cmp::Ordering;
+
+/// Order in which a heap hands out its elements, the one comparing as `Less` coming first.
+///
+/// Closures of the form `|a: &T, b: &T| -> Ordering` are comparators as well.
+pub trait Compare<T> {
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+
+    /// Whether `a` comes strictly before `b`
+    fn lt(&self, a: &T, b: &T) -> bool {
+        self.compare(a, b) == Ordering::Less
+    }
+
+    /// Whether `a` comes before `b` or ties with it
+    fn le(&self, a: &T, b: &T) -> bool {
+        self.compare(a, b) != Ordering::Greater
+    }
+}
+
+/// Smallest element first, the natural order of a min-heap
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MinOrder;
+
+impl<T: Ord> Compare<T> for MinOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Largest element first, turning a heap into a max-heap
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MaxOrder;
+
+impl<T: Ord> Compare<T> for MaxOrder {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Element with the smallest key first, the key being extracted by a function
+#[derive(Clone, Copy, Debug)]
+pub struct ByKey<F>(pub F);
+
+impl<T, K: Ord, F: Fn(&T) -> K> Compare<T> for ByKey<F> {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> Ordering> Compare<T> for F {
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{ByKey, Compare, MaxOrder, MinOrder};
+
+    #[test]
+    fn test_comparators() {
+        assert_eq!(MinOrder.compare(&1, &2), Ordering::Less);
+        assert_eq!(MaxOrder.compare(&1, &2), Ordering::Greater);
+        assert!(MaxOrder.le(&2, &2));
+        assert!(!MaxOrder.lt(&2, &2));
+
+        let by_length = ByKey(|s: &&str| s.len());
+        assert!(by_length.lt(&"ab", &"abc"));
+        assert_eq!(by_length.compare(&"ab", &"cd"), Ordering::Equal);
+
+        let closure = |a: &i32, b: &i32| (a % 10).cmp(&(b % 10));
+        assert!(closure.lt(&21, &12));
+    }
+}
@@ -0,0 +1,223 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, TryLockError};
+
+use rand::Rng;
+
+use super::binary_heap_vec::BinaryHeapVec;
+use super::{ConcurrentHeap, Heap};
+
+/// Queue with its own lock, aligned so that neighbouring locks do not share a cache line
+#[repr(align(64))]
+struct Stripe<T>(Mutex<BinaryHeapVec<T>>);
+
+/// Relaxed concurrent priority queue spreading elements over independently locked heaps.
+///
+/// Pushes go to a random heap. Pops look at two random heaps and take the better of
+/// their first elements, which keeps popped elements close to the top on average while
+/// threads rarely wait for each other. Locks are only tried, so a thread finding a heap
+/// busy simply picks other ones.
+pub struct MultiQueue<T> {
+    stripes: Vec<Stripe<T>>,
+    size: AtomicUsize,
+}
+
+impl<T: Ord> MultiQueue<T> {
+    /// Create a queue over a number of heaps, at least two.
+    ///
+    /// More heaps mean less contention but pops further from the top.
+    pub fn new(stripes: usize) -> Self {
+        assert!(stripes >= 2, "A multi-queue needs at least two heaps");
+
+        MultiQueue {
+            stripes: (0..stripes)
+                .map(|_| Stripe(Mutex::new(BinaryHeapVec::new())))
+                .collect(),
+            size: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a queue with two heaps per thread expected to use it
+    pub fn with_threads(threads: usize) -> Self {
+        Self::new(2 * threads.max(1))
+    }
+
+    fn try_lock(&self, stripe: usize) -> Option<MutexGuard<'_, BinaryHeapVec<T>>> {
+        match self.stripes[stripe].0.try_lock() {
+            Ok(heap) => Some(heap),
+            Err(TryLockError::WouldBlock) => None,
+            Err(TryLockError::Poisoned(_)) => panic!("Multi-queue heap lock poisoned"),
+        }
+    }
+}
+
+impl<T: Ord + Send> ConcurrentHeap<T> for MultiQueue<T> {
+    fn push(&self, element: T) {
+        let mut rng = rand::thread_rng();
+
+        loop {
+            if let Some(mut heap) = self.try_lock(rng.gen_range(0..self.stripes.len())) {
+                heap.push(element);
+                self.size.fetch_add(1, Ordering::Release);
+                return;
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        let mut rng = rand::thread_rng();
+        let count = self.stripes.len();
+
+        // Pushes count an element only once it is in a heap, so a positive size means
+        // there is an element to find unless another thread gets to it first
+        while self.size.load(Ordering::Acquire) > 0 {
+            let first = rng.gen_range(0..count);
+            let second = (first + rng.gen_range(1..count)) % count;
+
+            let (Some(mut first), Some(mut second)) = (self.try_lock(first), self.try_lock(second))
+            else {
+                continue;
+            };
+
+            let take_first = match (first.peek(), second.peek()) {
+                (Some(x), Some(y)) => x <= y,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => continue,
+            };
+
+            let element = if take_first {
+                first.pop()
+            } else {
+                second.pop()
+            };
+            self.size.fetch_sub(1, Ordering::AcqRel);
+            return element;
+        }
+
+        None
+    }
+
+    fn size(&self) -> usize {
+        self.size.load(Ordering::Acquire)
+    }
+}
+
+/// Any heap behind a single lock, as a strict baseline for relaxed queues
+impl<T: Send, H: Heap<T> + Send> ConcurrentHeap<T> for Mutex<H> {
+    fn push(&self, element: T) {
+        self.lock().unwrap().push(element);
+    }
+
+    fn pop(&self) -> Option<T> {
+        self.lock().unwrap().pop()
+    }
+
+    fn size(&self) -> usize {
+        self.lock().unwrap().size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::thread;
+
+    use crate::ds::heap::binary_heap_vec::BinaryHeapVec;
+    use crate::ds::heap::ConcurrentHeap;
+
+    use super::MultiQueue;
+
+    fn drain<Q: ConcurrentHeap<i32>>(queue: &Q) -> Vec<i32> {
+        std::iter::from_fn(|| queue.pop()).collect()
+    }
+
+    /// Push from several threads, then pop from several threads, and expect every element
+    /// to come out exactly once
+    fn check_threads<Q: ConcurrentHeap<i32>>(queue: Q) {
+        thread::scope(|scope| {
+            for t in 0..4 {
+                let queue = &queue;
+                scope.spawn(move || {
+                    for i in 0..5_000 {
+                        queue.push(i * 4 + t);
+                    }
+                });
+            }
+        });
+        assert_eq!(queue.size(), 20_000);
+
+        let mut popped: Vec<i32> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4).map(|_| scope.spawn(|| drain(&queue))).collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        });
+
+        popped.sort();
+        assert_eq!(popped, (0..20_000).collect::<Vec<_>>());
+        assert_eq!(queue.size(), 0);
+        assert_eq!(queue.pop(), None);
+    }
+
+    #[test]
+    fn test_threads() {
+        check_threads(MultiQueue::with_threads(4));
+        check_threads(Mutex::new(BinaryHeapVec::new()));
+    }
+
+    #[test]
+    fn test_mixed_operations() {
+        let queue = MultiQueue::new(8);
+
+        // Every thread pops one element for every two it pushes
+        let popped: usize = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|t| {
+                    let queue = &queue;
+                    scope.spawn(move || {
+                        let mut popped = 0;
+                        for i in 0..10_000 {
+                            queue.push(i * 4 + t);
+                            if i % 2 == 1 && queue.pop().is_some() {
+                                popped += 1;
+                            }
+                        }
+                        popped
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .sum()
+        });
+
+        assert_eq!(queue.size(), 40_000 - popped);
+        assert_eq!(drain(&queue).len(), 40_000 - popped);
+    }
+
+    #[test]
+    fn test_relaxed_order() {
+        let queue = MultiQueue::new(8);
+        for i in (0..10_000).rev() {
+            queue.push(i);
+        }
+
+        // Pops stay close to the top, a few positions off per heap on average
+        let popped = drain(&queue);
+        let rank_error: usize = popped
+            .iter()
+            .enumerate()
+            .map(|(position, &element)| position.abs_diff(element as usize))
+            .sum();
+        assert!(rank_error / popped.len() < 100);
+
+        let mut sorted = popped;
+        sorted.sort();
+        assert_eq!(sorted, (0..10_000).collect::<Vec<_>>());
+
+        let strict = Mutex::new(BinaryHeapVec::from_vec((0..100).rev().collect()));
+        assert_eq!(drain(&strict), (0..100).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,531 @@
+use super::compare::{Compare, MinOrder};
+use super::handle::{Handle, Slab};
+use super::invariants::{self, InvariantViolation};
+use super::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
+
+/// Bookkeeping of a vector heap told about every move of its elements, so that an
+/// element can be found again through a handle
+pub trait PositionIndex: Default {
+    /// Record a new last element, inserted without a handle
+    fn push(&mut self);
+
+    fn swap(&mut self, first: usize, second: usize);
+
+    /// Forget the last element, releasing its handle if it had one
+    fn pop(&mut self);
+
+    /// Forget all elements, invalidating every handle
+    fn clear(&mut self);
+
+    /// Check that the records match the `len` elements of the heap
+    fn check(&self, _len: usize) -> Result<(), InvariantViolation> {
+        Ok(())
+    }
+}
+
+/// Position index of heaps without handles, keeping nothing
+#[derive(Default)]
+pub struct NoPositions;
+
+impl PositionIndex for NoPositions {
+    fn push(&mut self) {}
+
+    fn swap(&mut self, _first: usize, _second: usize) {}
+
+    fn pop(&mut self) {}
+
+    fn clear(&mut self) {}
+}
+
+/// Position index of addressable heaps.
+///
+/// Elements inserted with a handle keep a slot in `handles` that records their current
+/// position, so every move of an element updates its slot.
+#[derive(Default)]
+pub struct HandlePositions {
+    slots: Vec<Option<usize>>,
+    handles: Slab<usize>,
+}
+
+impl HandlePositions {
+    /// Record a new last element at `index`, returning a handle to it
+    fn insert(&mut self, index: usize) -> Handle {
+        let handle = self.handles.insert(index);
+        self.slots.push(Some(handle.index()));
+        handle
+    }
+
+    /// Current position of the element behind a handle, if it is still in the heap
+    fn position(&self, handle: Handle) -> Option<usize> {
+        self.handles.resolve(handle).map(|slot| self.handles[slot])
+    }
+}
+
+impl PositionIndex for HandlePositions {
+    fn push(&mut self) {
+        self.slots.push(None);
+    }
+
+    fn swap(&mut self, first: usize, second: usize) {
+        self.slots.swap(first, second);
+
+        for index in [first, second] {
+            if let Some(slot) = self.slots[index] {
+                self.handles[slot] = index;
+            }
+        }
+    }
+
+    fn pop(&mut self) {
+        if let Some(slot) = self.slots.pop().unwrap() {
+            self.handles.remove(slot);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.slots.clear();
+        self.handles.clear();
+    }
+
+    /// Checks that handle slots match positions
+    fn check(&self, len: usize) -> Result<(), InvariantViolation> {
+        let mut tracked = 0;
+        for (index, slot) in self.slots.iter().enumerate() {
+            if let Some(slot) = *slot {
+                if self.handles.get(slot) != Some(&index) {
+                    return Err(InvariantViolation::Handle { slot });
+                }
+                tracked += 1;
+            }
+        }
+
+        if self.slots.len() != len || tracked != self.handles.len() {
+            return Err(InvariantViolation::Size {
+                reachable: tracked,
+                expected: self.handles.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Heap with `D` children per node stored in a vector, ordered by a comparator.
+///
+/// Every move of an element is reported to the position index `P`, which keeps nothing
+/// unless the heap hands out handles, as [`AddressableDAryHeap`] does.
+pub struct DAryHeapVec<T, const D: usize, C = MinOrder, P = NoPositions> {
+    data: Vec<T>,
+    positions: P,
+    compare: C,
+}
+
+/// D-ary heap whose elements can be updated or removed through handles, at the cost of
+/// a position slot per element and of updating it on every move
+pub type AddressableDAryHeap<T, const D: usize, C = MinOrder> =
+    DAryHeapVec<T, D, C, HandlePositions>;
+
+impl<T: Ord, const D: usize, P: PositionIndex> DAryHeapVec<T, D, MinOrder, P> {
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+
+    /// Build a heap from a vector in O(n) with Floyd's bottom-up heapify
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(vec, MinOrder)
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> DAryHeapVec<T, D, C, P> {
+    pub fn with_comparator(compare: C) -> Self {
+        const { assert!(D >= 2, "Heap arity must be at least 2") };
+
+        DAryHeapVec {
+            data: vec![],
+            positions: P::default(),
+            compare,
+        }
+    }
+
+    pub fn from_vec_with_comparator(vec: Vec<T>, compare: C) -> Self {
+        let mut heap = Self::with_comparator(compare);
+        heap.append_and_heapify(vec);
+        heap
+    }
+
+    /// Append elements without handles and restore the heap property over the whole vector
+    fn append_and_heapify(&mut self, mut elements: Vec<T>) {
+        for _ in 0..elements.len() {
+            self.positions.push();
+        }
+        self.data.append(&mut elements);
+        self.heapify();
+        invariants::debug_check(self);
+    }
+
+    /// Swap two elements along with their position records.
+    ///
+    /// Elements are only ever moved, never cloned, so any element type can be stored.
+    fn swap(&mut self, first: usize, second: usize) {
+        self.data.swap(first, second);
+        self.positions.swap(first, second);
+    }
+
+    /// Bubble up element at given index
+    fn sift_up(&mut self, index: usize) {
+        let mut child_index = index;
+
+        // While we are not at the top
+        while child_index > 0 {
+            // Find index of my parent
+            let parent_index = (child_index - 1) / D;
+
+            // If child is larger or equal to the parent, we stop
+            if self
+                .compare
+                .le(&self.data[parent_index], &self.data[child_index])
+            {
+                break;
+            }
+
+            // Otherwise, the child takes the place of its parent
+            self.swap(child_index, parent_index);
+            child_index = parent_index;
+        }
+    }
+
+    fn sift_down(&mut self, index: usize) {
+        self.sift_down_within(index, self.data.len());
+    }
+
+    /// Sift down considering only the first `count` elements as part of the heap
+    fn sift_down_within(&mut self, index: usize, count: usize) {
+        let mut parent_index = index;
+
+        loop {
+            let first_child = D * parent_index + 1;
+            if first_child >= count {
+                break;
+            }
+
+            // Find the smallest of up to D children
+            let mut child_index = first_child;
+            for index in first_child + 1..count.min(first_child + D) {
+                if self.compare.lt(&self.data[index], &self.data[child_index]) {
+                    child_index = index;
+                }
+            }
+
+            if self
+                .compare
+                .le(&self.data[parent_index], &self.data[child_index])
+            {
+                break;
+            }
+
+            self.swap(parent_index, child_index);
+            parent_index = child_index;
+        }
+    }
+
+    /// Restore the heap property over the whole vector, bottom-up in O(n)
+    fn heapify(&mut self) {
+        let internal_nodes = (self.data.len() + D - 2) / D;
+
+        for index in (0..internal_nodes).rev() {
+            self.sift_down(index);
+        }
+    }
+
+    /// Remove the element at a given index, releasing its handle slot
+    fn remove_at(&mut self, index: usize) -> T {
+        self.swap(index, self.data.len() - 1);
+
+        let removed_value = self.data.pop().unwrap();
+        self.positions.pop();
+
+        // The last element moved into the hole and may need to go either way
+        if index < self.data.len() {
+            if index > 0 {
+                self.sift_up(index);
+            }
+            self.sift_down(index);
+        }
+
+        invariants::debug_check(self);
+        removed_value
+    }
+}
+
+impl<T, const D: usize, C: Compare<T> + Default, P: PositionIndex> Default
+    for DAryHeapVec<T, D, C, P>
+{
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> Heap<T> for DAryHeapVec<T, D, C, P> {
+    fn push(&mut self, value: T) {
+        self.data.push(value);
+        self.positions.push();
+        self.sift_up(self.data.len() - 1);
+        invariants::debug_check(self);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(self.remove_at(0))
+    }
+
+    fn peek(&self) -> Option<&T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(&self.data[0])
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.positions.clear();
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        self.positions.clear();
+        Box::new(self.data.drain(..))
+    }
+
+    /// Heapsort in place: the first element is repeatedly swapped behind a shrinking
+    /// heap prefix, which leaves the vector in reverse heap order
+    fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.positions.swap(0, end);
+            self.sift_down_within(0, end);
+        }
+
+        self.data.reverse();
+        self.data
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> IntoIterator for DAryHeapVec<T, D, C, P> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> MergeableHeap<T>
+    for DAryHeapVec<T, D, C, P>
+{
+    /// An array heap has no cheap meld, so both vectors are concatenated and
+    /// heapified again in O(n + m). Handles of `other` are consumed along with it.
+    fn meld(&mut self, other: Self) {
+        self.append_and_heapify(other.data);
+    }
+}
+
+impl<T, const D: usize, C: Compare<T> + Default, P: PositionIndex> FromIterator<T>
+    for DAryHeapVec<T, D, C, P>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec_with_comparator(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> Extend<T> for DAryHeapVec<T, D, C, P> {
+    /// Pushes the new elements one by one, unless rebuilding the whole heap in O(n + k)
+    /// is cheaper than k pushes of O(log(n + k)) each
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let elements: Vec<T> = iter.into_iter().collect();
+        let total = self.data.len() + elements.len();
+        let push_cost = elements.len() * (usize::BITS - total.leading_zeros()) as usize;
+
+        if push_cost > total {
+            self.append_and_heapify(elements);
+        } else {
+            for element in elements {
+                self.push(element);
+            }
+        }
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>, P: PositionIndex> HeapInvariants
+    for DAryHeapVec<T, D, C, P>
+{
+    /// Checks that no element comes before its parent and that the position index matches
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        for child in 1..self.data.len() {
+            let parent = (child - 1) / D;
+            if self.compare.lt(&self.data[child], &self.data[parent]) {
+                return Err(InvariantViolation::HeapOrder { parent, child });
+            }
+        }
+
+        self.positions.check(self.data.len())
+    }
+}
+
+impl<T, const D: usize, C: Compare<T>> AddressableHeap<T> for AddressableDAryHeap<T, D, C> {
+    fn insert(&mut self, value: T) -> Handle {
+        let index = self.data.len();
+        let handle = self.positions.insert(index);
+
+        self.data.push(value);
+        self.sift_up(index);
+        invariants::debug_check(self);
+
+        handle
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.positions
+            .position(handle)
+            .map(|index| &self.data[index])
+    }
+
+    fn decrease_key(&mut self, handle: Handle, value: T) {
+        let index = self
+            .positions
+            .position(handle)
+            .expect("Invalid heap handle");
+        assert!(
+            self.compare.le(&value, &self.data[index]),
+            "New key is greater than the current key"
+        );
+
+        self.data[index] = value;
+        self.sift_up(index);
+        invariants::debug_check(self);
+    }
+
+    fn delete(&mut self, handle: Handle) -> Option<T> {
+        let index = self.positions.position(handle)?;
+
+        Some(self.remove_at(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ds::heap::invariants::InvariantViolation;
+    use crate::ds::heap::{Heap, HeapInvariants, MergeableHeap};
+
+    use super::DAryHeapVec;
+
+    fn check_heap<const D: usize>() {
+        let mut heap = DAryHeapVec::<i32, D>::new();
+
+        // Test inserting elements
+        for i in 1..=100 {
+            heap.push(i);
+        }
+
+        // Test inserting smaller elements after creating the heap
+        for i in -100..=0 {
+            heap.push(i);
+        }
+
+        // Test the smallest element in the heap
+        assert_eq!(heap.peek(), Some(&-100));
+
+        // Test popping all elements from the heap
+        for i in -100..=100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+
+        // Test heap is empty
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    fn check_from_vec<const D: usize>() {
+        let mut heap = DAryHeapVec::<i32, D>::from_vec((0..100).rev().collect());
+        heap.extend([-1, 150]);
+        heap.extend(100..150);
+
+        assert_eq!(heap.size(), 152);
+        for i in -1..=150 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+
+        let heap: DAryHeapVec<i32, D> = [5, 3, 8, 1].into_iter().collect();
+        assert_eq!(heap.peek(), Some(&1));
+    }
+
+    fn check_meld<const D: usize>() {
+        let mut heap = DAryHeapVec::<i32, D>::new();
+        let mut other = DAryHeapVec::<i32, D>::new();
+
+        for i in (0..100).rev().step_by(2) {
+            heap.push(i);
+        }
+        for i in (0..100).step_by(2) {
+            other.push(i);
+        }
+
+        heap.meld(other);
+
+        for i in 0..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_heap() {
+        check_heap::<3>();
+        check_heap::<4>();
+        check_heap::<8>();
+    }
+
+    #[test]
+    fn test_invariants() {
+        let mut heap = DAryHeapVec::<i32, 3>::from_vec((0..50).rev().collect());
+        assert_eq!(heap.check_invariants(), Ok(()));
+
+        heap.data.swap(0, 4);
+        assert_eq!(
+            heap.check_invariants(),
+            Err(InvariantViolation::HeapOrder {
+                parent: 0,
+                child: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_from_vec() {
+        check_from_vec::<3>();
+        check_from_vec::<4>();
+        check_from_vec::<8>();
+    }
+
+    #[test]
+    fn test_meld() {
+        check_meld::<3>();
+        check_meld::<4>();
+        check_meld::<8>();
+    }
+}
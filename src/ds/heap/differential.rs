@@ -0,0 +1,261 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::panic::{self, AssertUnwindSafe};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use super::binary_heap_vec::{AddressableBinaryHeap, BinaryHeapVec};
+use super::binomial_heap::{AddressableBinomialHeap, ArenaBinomialHeap, BinomialHeap};
+use super::d_ary_heap_vec::DAryHeapVec;
+use super::dual_heap::DualHeap;
+use super::fibonacci_heap::FibonacciHeap;
+use super::interval_heap::IntervalHeap;
+use super::leftist_heap::{AddressableLeftistHeap, ArenaLeftistHeap, LeftistHeap};
+use super::min_max_heap::MinMaxHeap;
+use super::pairing_heap::{PairingHeap, PopStrategy};
+use super::rank_pairing_heap::{RankPairingHeap, RankRule};
+use super::skew_heap::SkewHeap;
+use super::weak_heap::WeakHeap;
+use super::Heap;
+
+/// Operation applied alike to a heap under test and to the `BinaryHeap` oracle
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operation {
+    Push(i32),
+    Pop,
+    Peek,
+    Clear,
+    Size,
+}
+
+/// Generate operations over a small key range, so that duplicate keys are common and
+/// pushes and pops interleave at every heap size
+fn random_operations(seed: u64, count: usize) -> Vec<Operation> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let max_key = rng.gen_range(1..=64);
+
+    (0..count)
+        .map(|_| match rng.gen_range(0..100) {
+            0..=49 => Operation::Push(rng.gen_range(-max_key..=max_key)),
+            50..=79 => Operation::Pop,
+            80..=89 => Operation::Peek,
+            90 => Operation::Clear,
+            _ => Operation::Size,
+        })
+        .collect()
+}
+
+/// Run operations on a fresh heap and on the oracle, describing the first disagreement.
+///
+/// A panic inside the heap counts as a disagreement as well.
+fn run<H: Heap<i32>>(make: &dyn Fn() -> H, operations: &[Operation]) -> Result<(), String> {
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        let mut heap = make();
+        let mut oracle = BinaryHeap::new();
+
+        for (step, &operation) in operations.iter().enumerate() {
+            let (actual, expected) = match operation {
+                Operation::Push(key) => {
+                    heap.push(key);
+                    oracle.push(Reverse(key));
+                    continue;
+                }
+                Operation::Pop => (heap.pop(), oracle.pop().map(|Reverse(key)| key)),
+                Operation::Peek => (heap.peek().copied(), oracle.peek().map(|&Reverse(key)| key)),
+                Operation::Clear => {
+                    heap.clear();
+                    oracle.clear();
+                    (Some(heap.size() as i32), Some(0))
+                }
+                Operation::Size => (Some(heap.size() as i32), Some(oracle.len() as i32)),
+            };
+
+            if actual != expected {
+                return Err(format!(
+                    "{operation:?} at step {step} returned {actual:?} instead of {expected:?}"
+                ));
+            }
+        }
+
+        Ok(())
+    }));
+
+    outcome.unwrap_or_else(|_| Err("heap panicked".to_string()))
+}
+
+/// Keys closer to zero to try in place of a pushed key while shrinking
+fn simpler_keys(key: i32) -> Vec<i32> {
+    match key {
+        0 => vec![],
+        _ => vec![0, key / 2],
+    }
+}
+
+/// Shrink a failing sequence while it keeps failing, first by deleting chunks of
+/// operations of decreasing length, then by making pushed keys closer to zero.
+///
+/// Every push of a key is rewritten at once, so failures depending on equal keys
+/// still shrink.
+fn shrink(operations: &[Operation], fails: impl Fn(&[Operation]) -> bool) -> Vec<Operation> {
+    let mut current = operations.to_vec();
+
+    loop {
+        let mut progress = false;
+
+        let mut chunk = current.len() / 2;
+        while chunk > 0 {
+            let mut start = 0;
+            while start + chunk <= current.len() {
+                let candidate = [&current[..start], &current[start + chunk..]].concat();
+                if fails(&candidate) {
+                    current = candidate;
+                    progress = true;
+                } else {
+                    start += chunk;
+                }
+            }
+            chunk /= 2;
+        }
+
+        for index in 0..current.len() {
+            let Operation::Push(key) = current[index] else {
+                continue;
+            };
+
+            for simpler in simpler_keys(key) {
+                let candidate: Vec<_> = current
+                    .iter()
+                    .map(|&operation| match operation {
+                        Operation::Push(other) if other == key => Operation::Push(simpler),
+                        operation => operation,
+                    })
+                    .collect();
+                if fails(&candidate) {
+                    current = candidate;
+                    progress = true;
+                    break;
+                }
+            }
+        }
+
+        if !progress {
+            return current;
+        }
+    }
+}
+
+/// Find the shortest failing sequence over a number of seeds, returning it with its failure
+fn find_failure<H: Heap<i32>>(
+    make: &dyn Fn() -> H,
+    seeds: u64,
+) -> Option<(Vec<Operation>, String)> {
+    (0..seeds).find_map(|seed| {
+        let operations = random_operations(seed, 300);
+        run(make, &operations).err()?;
+
+        let minimal = shrink(&operations, |candidate| run(make, candidate).is_err());
+        let failure = run(make, &minimal).unwrap_err();
+        Some((minimal, failure))
+    })
+}
+
+/// Run random operation sequences on a heap and on `std::collections::BinaryHeap` side by
+/// side, panicking with the minimal sequence any disagreement shrinks down to
+fn check_heap<H: Heap<i32>>(name: &str, make: &dyn Fn() -> H) {
+    if let Some((minimal, failure)) = find_failure(make, 200) {
+        panic!("{name} disagrees with BinaryHeap: {failure}\nMinimal reproducer: {minimal:?}");
+    }
+}
+
+#[test]
+fn test_array_heaps() {
+    check_heap("BinaryHeapVec", &BinaryHeapVec::<i32>::new);
+    check_heap("AddressableBinaryHeap", &AddressableBinaryHeap::<i32>::new);
+    check_heap("DAryHeapVec<3>", &DAryHeapVec::<i32, 3>::new);
+    check_heap("DAryHeapVec<4>", &DAryHeapVec::<i32, 4>::new);
+    check_heap("DAryHeapVec<8>", &DAryHeapVec::<i32, 8>::new);
+    check_heap("WeakHeap", &WeakHeap::<i32>::new);
+}
+
+#[test]
+fn test_node_heaps() {
+    check_heap("LeftistHeap", &LeftistHeap::<i32>::new);
+    check_heap(
+        "AddressableLeftistHeap",
+        &AddressableLeftistHeap::<i32>::new,
+    );
+    check_heap("SkewHeap", &SkewHeap::<i32>::new);
+    check_heap("BinomialHeap", &BinomialHeap::<i32>::new);
+    check_heap(
+        "AddressableBinomialHeap",
+        &AddressableBinomialHeap::<i32>::new,
+    );
+    check_heap("FibonacciHeap", &FibonacciHeap::<i32>::new);
+
+    for strategy in [
+        PopStrategy::TwoPass,
+        PopStrategy::Multipass,
+        PopStrategy::FrontToBack,
+    ] {
+        check_heap(&format!("PairingHeap {strategy:?}"), &|| {
+            PairingHeap::<i32>::with_strategy(strategy)
+        });
+    }
+
+    for rule in [RankRule::Type1, RankRule::Type2] {
+        check_heap(&format!("RankPairingHeap {rule:?}"), &|| {
+            RankPairingHeap::<i32>::with_rule(rule)
+        });
+    }
+
+    check_heap("ArenaLeftistHeap", &ArenaLeftistHeap::<i32>::new);
+    check_heap("ArenaBinomialHeap", &ArenaBinomialHeap::<i32>::new);
+}
+
+#[test]
+fn test_double_ended_heaps() {
+    check_heap("MinMaxHeap", &MinMaxHeap::<i32>::new);
+    check_heap("IntervalHeap", &IntervalHeap::<i32>::new);
+    check_heap("DualHeap", &DualHeap::<i32>::new);
+}
+
+/// Heap that silently drops a key equal to its current minimum
+#[derive(Default)]
+struct DeduplicatingHeap {
+    inner: BinaryHeapVec<i32>,
+}
+
+impl Heap<i32> for DeduplicatingHeap {
+    fn peek(&self) -> Option<&i32> {
+        self.inner.peek()
+    }
+
+    fn pop(&mut self) -> Option<i32> {
+        self.inner.pop()
+    }
+
+    fn push(&mut self, element: i32) {
+        if self.inner.peek() != Some(&element) {
+            self.inner.push(element);
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &i32> + '_> {
+        self.inner.iter()
+    }
+}
+
+#[test]
+fn test_shrinking() {
+    let (minimal, failure) =
+        find_failure(&DeduplicatingHeap::default, 200).expect("Duplicate keys went unnoticed");
+
+    // Two equal pushes, then operations observing the missing element
+    assert!(minimal.len() <= 4, "{failure}: {minimal:?}");
+    assert_eq!(minimal[..2], [Operation::Push(0), Operation::Push(0)]);
+}
@@ -0,0 +1,251 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::binary_heap_vec::BinaryHeapVec;
+use super::compare::MaxOrder;
+use super::{DoubleEndedHeap, Heap};
+
+/// Double-ended heap built from a min-heap and a max-heap holding the same elements.
+///
+/// An element popped from one side stays behind in the other one and is recorded
+/// as pending there, to be discarded once it reaches the top of that side.
+pub struct DualHeap<T> {
+    min_heap: BinaryHeapVec<T>,
+    max_heap: BinaryHeapVec<T, MaxOrder>,
+    min_pending: HashMap<T, usize>,
+    max_pending: HashMap<T, usize>,
+    size: usize,
+}
+
+impl<T: Ord + Clone + Hash> DualHeap<T> {
+    pub fn new() -> Self {
+        DualHeap {
+            min_heap: BinaryHeapVec::new(),
+            max_heap: BinaryHeapVec::with_comparator(MaxOrder),
+            min_pending: HashMap::new(),
+            max_pending: HashMap::new(),
+            size: 0,
+        }
+    }
+
+    /// Consume a pending deletion of a value, if there is one
+    fn take_pending(pending: &mut HashMap<T, usize>, value: &T) -> bool {
+        match pending.get_mut(value) {
+            None => false,
+            Some(count) => {
+                *count -= 1;
+                if *count == 0 {
+                    pending.remove(value);
+                }
+                true
+            }
+        }
+    }
+
+    fn mark_pending(pending: &mut HashMap<T, usize>, value: T) {
+        *pending.entry(value).or_insert(0) += 1;
+    }
+
+    /// Discard elements already popped from the other side until both tops are live
+    fn purge(&mut self) {
+        while let Some(top) = self.min_heap.peek() {
+            if !Self::take_pending(&mut self.min_pending, top) {
+                break;
+            }
+            self.min_heap.pop();
+        }
+
+        while let Some(top) = self.max_heap.peek() {
+            if !Self::take_pending(&mut self.max_pending, top) {
+                break;
+            }
+            self.max_heap.pop();
+        }
+    }
+}
+
+/// Drop the elements of an iterator that are pending deletion, using up their counts
+fn skip_pending<T: Eq + Hash, V: Borrow<T>>(
+    iter: impl Iterator<Item = V>,
+    mut pending: HashMap<T, usize>,
+) -> impl Iterator<Item = V> {
+    iter.filter(move |value| match pending.get_mut(value.borrow()) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            false
+        }
+        _ => true,
+    })
+}
+
+impl<T: Ord + Clone + Hash> Default for DualHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone + Hash> DoubleEndedHeap<T> for DualHeap<T> {
+    fn peek_min(&self) -> Option<&T> {
+        self.min_heap.peek()
+    }
+
+    fn peek_max(&self) -> Option<&T> {
+        self.max_heap.peek()
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        let value = self.min_heap.pop()?;
+
+        Self::mark_pending(&mut self.max_pending, value.clone());
+        self.size -= 1;
+        self.purge();
+
+        Some(value)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        let value = self.max_heap.pop()?;
+
+        Self::mark_pending(&mut self.min_pending, value.clone());
+        self.size -= 1;
+        self.purge();
+
+        Some(value)
+    }
+}
+
+impl<T: Ord + Clone + Hash> Heap<T> for DualHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.peek_min()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    fn push(&mut self, element: T) {
+        self.max_heap.push(element.clone());
+        self.min_heap.push(element);
+        self.size += 1;
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Elements of the min-heap side, minus those already popped from the max side
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(skip_pending(self.min_heap.iter(), self.min_pending.clone()))
+    }
+
+    fn clear(&mut self) {
+        self.min_heap.clear();
+        self.max_heap.clear();
+        self.min_pending.clear();
+        self.max_pending.clear();
+        self.size = 0;
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        let pending = std::mem::take(&mut self.min_pending);
+        self.max_heap.clear();
+        self.max_pending.clear();
+        self.size = 0;
+
+        Box::new(skip_pending(self.min_heap.drain(), pending))
+    }
+}
+
+impl<T: Ord + Clone + Hash> IntoIterator for DualHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order
+    fn into_iter(self) -> Self::IntoIter {
+        skip_pending(self.min_heap.into_iter(), self.min_pending)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::{DoubleEndedHeap, Heap};
+
+    use super::DualHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = DualHeap::<i32>::new();
+
+        heap.push(3);
+        heap.push(1);
+        heap.push(4);
+        heap.push(2);
+
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&4));
+
+        assert_eq!(heap.pop_max(), Some(4));
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(3));
+        assert_eq!(heap.pop_min(), Some(2));
+        assert_eq!(heap.pop_max(), None);
+        assert_eq!(heap.pop_min(), None);
+        assert_eq!(heap.size(), 0);
+    }
+
+    #[test]
+    fn test_iter_skips_popped() {
+        let mut heap: DualHeap<i32> = (0..10).fold(DualHeap::new(), |mut heap, i| {
+            heap.push(i);
+            heap
+        });
+
+        // Elements popped from the max side still sit in the min-heap until they surface
+        assert_eq!(heap.pop_max(), Some(9));
+        assert_eq!(heap.pop_max(), Some(8));
+
+        let mut elements: Vec<i32> = heap.iter().copied().collect();
+        elements.sort();
+        assert_eq!(elements, (0..8).collect::<Vec<_>>());
+
+        let mut elements: Vec<i32> = heap.into_iter().collect();
+        elements.sort();
+        assert_eq!(elements, (0..8).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut heap = DualHeap::<i32>::new();
+        let mut sorted = Vec::new();
+
+        for _ in 0..5_000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let value = rng.gen_range(0..100);
+                    heap.push(value);
+                    let position = sorted.partition_point(|&x| x < value);
+                    sorted.insert(position, value);
+                }
+                2 => assert_eq!(
+                    heap.pop_min(),
+                    (!sorted.is_empty()).then(|| sorted.remove(0))
+                ),
+                _ => assert_eq!(heap.pop_max(), sorted.pop()),
+            }
+
+            assert_eq!(heap.peek_min(), sorted.first());
+            assert_eq!(heap.peek_max(), sorted.last());
+            assert_eq!(heap.size(), sorted.len());
+        }
+    }
+}
@@ -0,0 +1,461 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::mem::size_of;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::binary_heap_vec::BinaryHeapVec;
+use super::instrumented::Counted;
+use super::Heap;
+
+/// Largest read buffer given to a single run
+const BLOCK_SIZE: usize = 16 * 1024;
+
+/// Element with a fixed-size binary encoding, so that it can be spilled to disk
+pub trait Record: Sized {
+    /// Number of bytes of an encoded element
+    const SIZE: usize;
+
+    fn encode(&self, bytes: &mut [u8]);
+
+    fn decode(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_record_for_integers {
+    ($($t:ty),*) => {
+        $(
+            impl Record for $t {
+                const SIZE: usize = std::mem::size_of::<$t>();
+
+                fn encode(&self, bytes: &mut [u8]) {
+                    bytes.copy_from_slice(&self.to_le_bytes());
+                }
+
+                fn decode(bytes: &[u8]) -> Self {
+                    <$t>::from_le_bytes(bytes.try_into().unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_record_for_integers!(i32, i64, u32, u64, usize);
+
+impl<A: Record, B: Record> Record for (A, B) {
+    const SIZE: usize = A::SIZE + B::SIZE;
+
+    fn encode(&self, bytes: &mut [u8]) {
+        self.0.encode(&mut bytes[..A::SIZE]);
+        self.1.encode(&mut bytes[A::SIZE..]);
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        (A::decode(&bytes[..A::SIZE]), B::decode(&bytes[A::SIZE..]))
+    }
+}
+
+impl<T: Record> Record for Counted<T> {
+    const SIZE: usize = T::SIZE;
+
+    fn encode(&self, bytes: &mut [u8]) {
+        self.0.encode(bytes);
+    }
+
+    fn decode(bytes: &[u8]) -> Self {
+        Counted(T::decode(bytes))
+    }
+}
+
+/// Bytes moved between memory and disk by an external heap
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IoStats {
+    pub bytes_written: u64,
+    pub bytes_read: u64,
+    /// Runs written, by spilling the buffer or by merging other runs
+    pub runs_written: usize,
+}
+
+/// Sorted file of elements, read back one element at a time
+struct Run {
+    reader: BufReader<File>,
+    path: PathBuf,
+    /// Elements left in the file
+    remaining: usize,
+    /// Number of merges the elements went through, runs being merged by level
+    level: usize,
+}
+
+/// Priority queue for more elements than fit in memory, spilling sorted runs to files.
+///
+/// Pushes go to an in-memory binary heap. When it holds half the memory budget, its
+/// elements are written out as a sorted run. Pops take the smaller of the buffer's first
+/// element and the first unread element of every run, those being kept in a second
+/// binary heap. Once a level collects as many runs as fit their read buffers into the
+/// other half of the budget, they are merged into a single run on the next level, so
+/// every element is rewritten O(log(n / M)) times for a budget of M.
+///
+/// The budget is approximate: every level holds up to its fan-in of runs, each with its
+/// own read buffer. Run files live in a fresh directory that is removed on drop.
+///
+/// An I/O error poisons the heap, since it can strike halfway through moving elements
+/// between memory and disk: elements may have been lost, and every later push or pop
+/// fails.
+pub struct ExternalHeap<T> {
+    buffer: BinaryHeapVec<T>,
+    buffer_capacity: usize,
+    /// First unread element of every run, along with the run's slot
+    heads: BinaryHeapVec<(T, usize)>,
+    runs: Vec<Option<Run>>,
+    /// Number of runs a level holds before they are merged
+    fan_in: usize,
+    block_size: usize,
+    parent: PathBuf,
+    directory: Option<PathBuf>,
+    next_run_id: usize,
+    /// Encoding of the element being written or read, kept to avoid allocating per element
+    scratch: Vec<u8>,
+    size: usize,
+    stats: IoStats,
+    poisoned: bool,
+}
+
+impl<T: Ord + Record> ExternalHeap<T> {
+    /// Create a heap using about `memory_budget` bytes, spilling to the system's temporary
+    /// directory
+    pub fn new(memory_budget: usize) -> Self {
+        Self::with_directory(memory_budget, std::env::temp_dir())
+    }
+
+    /// Create a heap spilling to a new directory created inside `parent` on the first spill
+    pub fn with_directory(memory_budget: usize, parent: impl Into<PathBuf>) -> Self {
+        let half = memory_budget / 2;
+
+        // The buffer is a plain binary heap without handle slots, so an element costs its
+        // in-memory size, and the vector is allocated up front so that it never grows past it
+        let buffer_capacity = (half / size_of::<T>().max(1)).max(1);
+
+        // Besides its read buffer, a run takes its entry in `runs` and its head in `heads`
+        let run_overhead = size_of::<Option<Run>>() + size_of::<(T, usize)>();
+        let fan_in = (half / (BLOCK_SIZE + run_overhead)).max(2);
+
+        ExternalHeap {
+            buffer: BinaryHeapVec::from_vec(Vec::with_capacity(buffer_capacity)),
+            buffer_capacity,
+            heads: BinaryHeapVec::new(),
+            runs: Vec::new(),
+            fan_in,
+            block_size: (half / fan_in)
+                .saturating_sub(run_overhead)
+                .clamp(T::SIZE, BLOCK_SIZE),
+            parent: parent.into(),
+            directory: None,
+            next_run_id: 0,
+            scratch: vec![0; T::SIZE],
+            size: 0,
+            stats: IoStats::default(),
+            poisoned: false,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn stats(&self) -> IoStats {
+        self.stats
+    }
+
+    /// Whether an earlier I/O error left the heap unusable
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Number of runs currently on disk
+    pub fn runs(&self) -> usize {
+        self.runs.iter().flatten().count()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        match (self.buffer.peek(), self.heads.peek()) {
+            (Some(x), Some((y, _))) => Some(x.min(y)),
+            (x, None) => x,
+            (None, Some((y, _))) => Some(y),
+        }
+    }
+
+    pub fn push(&mut self, element: T) -> io::Result<()> {
+        self.poisoning(|heap| {
+            heap.buffer.push(element);
+            heap.size += 1;
+
+            if heap.buffer.size() >= heap.buffer_capacity {
+                heap.spill()?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn pop(&mut self) -> io::Result<Option<T>> {
+        self.poisoning(|heap| {
+            let from_buffer = match (heap.buffer.peek(), heap.heads.peek()) {
+                (Some(x), Some((y, _))) => x <= y,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (None, None) => return Ok(None),
+            };
+
+            let element = if from_buffer {
+                heap.buffer.pop()
+            } else {
+                let (element, slot) = heap.heads.pop().unwrap();
+                if let Some(next) = heap.read_next(slot)? {
+                    heap.heads.push((next, slot));
+                }
+                Some(element)
+            };
+
+            heap.size -= 1;
+            Ok(element)
+        })
+    }
+
+    /// Run an operation that moves elements to or from disk, poisoning the heap if it fails
+    fn poisoning<R>(
+        &mut self,
+        operation: impl FnOnce(&mut Self) -> io::Result<R>,
+    ) -> io::Result<R> {
+        if self.poisoned {
+            return Err(io::Error::other(
+                "External heap is poisoned by an earlier I/O error",
+            ));
+        }
+
+        let result = operation(self);
+        self.poisoned = result.is_err();
+        result
+    }
+
+    fn directory(&mut self) -> io::Result<&Path> {
+        static HEAPS: AtomicUsize = AtomicUsize::new(0);
+
+        if self.directory.is_none() {
+            let directory = self.parent.join(format!(
+                "external-heap-{}-{}",
+                std::process::id(),
+                HEAPS.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&directory)?;
+            self.directory = Some(directory);
+        }
+        Ok(self.directory.as_deref().unwrap())
+    }
+
+    fn create_run(&mut self) -> io::Result<(PathBuf, BufWriter<File>)> {
+        let name = format!("run-{}", self.next_run_id);
+        self.next_run_id += 1;
+
+        let path = self.directory()?.join(name);
+        let writer = BufWriter::with_capacity(BLOCK_SIZE, File::create(&path)?);
+        Ok((path, writer))
+    }
+
+    fn write_element(&mut self, writer: &mut impl Write, element: &T) -> io::Result<()> {
+        element.encode(&mut self.scratch);
+        writer.write_all(&self.scratch)?;
+        self.stats.bytes_written += T::SIZE as u64;
+        Ok(())
+    }
+
+    /// Open a written run and put its first element among the heads
+    fn add_run(&mut self, path: PathBuf, count: usize, level: usize) -> io::Result<()> {
+        let run = Run {
+            reader: BufReader::with_capacity(self.block_size, File::open(&path)?),
+            path,
+            remaining: count,
+            level,
+        };
+
+        let slot = match self.runs.iter().position(Option::is_none) {
+            Some(slot) => slot,
+            None => {
+                self.runs.push(None);
+                self.runs.len() - 1
+            }
+        };
+        self.runs[slot] = Some(run);
+        self.stats.runs_written += 1;
+
+        if let Some(first) = self.read_next(slot)? {
+            self.heads.push((first, slot));
+        }
+        Ok(())
+    }
+
+    /// Read the next element of a run, deleting its file once it is exhausted
+    fn read_next(&mut self, slot: usize) -> io::Result<Option<T>> {
+        let run = self.runs[slot].as_mut().expect("Run slot is vacant");
+
+        if run.remaining == 0 {
+            let run = self.runs[slot].take().unwrap();
+            drop(run.reader);
+            fs::remove_file(run.path)?;
+            return Ok(None);
+        }
+
+        run.reader.read_exact(&mut self.scratch)?;
+        run.remaining -= 1;
+        self.stats.bytes_read += T::SIZE as u64;
+        Ok(Some(T::decode(&self.scratch)))
+    }
+
+    /// Write the buffer out as a sorted run on the first level, then merge full levels
+    fn spill(&mut self) -> io::Result<()> {
+        let mut elements = std::mem::take(&mut self.buffer).into_sorted_vec();
+        let (path, mut writer) = self.create_run()?;
+
+        for element in &elements {
+            self.write_element(&mut writer, element)?;
+        }
+        writer.flush()?;
+        self.add_run(path, elements.len(), 0)?;
+
+        // Hand the allocation back to the buffer
+        elements.clear();
+        self.buffer = BinaryHeapVec::from_vec(elements);
+
+        let mut level = 0;
+        loop {
+            let slots: Vec<usize> = (0..self.runs.len())
+                .filter(|&slot| matches!(&self.runs[slot], Some(run) if run.level == level))
+                .collect();
+            if slots.len() < self.fan_in {
+                return Ok(());
+            }
+
+            self.merge(&slots, level + 1)?;
+            level += 1;
+        }
+    }
+
+    /// Merge the remaining elements of some runs, their heads included, into a new run
+    fn merge(&mut self, slots: &[usize], level: usize) -> io::Result<()> {
+        let mut merging = BinaryHeapVec::new();
+        let mut others = Vec::new();
+        for (element, slot) in std::mem::take(&mut self.heads).drain() {
+            if slots.contains(&slot) {
+                merging.push((element, slot));
+            } else {
+                others.push((element, slot));
+            }
+        }
+        self.heads = BinaryHeapVec::from_vec(others);
+
+        let (path, mut writer) = self.create_run()?;
+        let mut count = 0;
+        while let Some((element, slot)) = merging.pop() {
+            self.write_element(&mut writer, &element)?;
+            count += 1;
+
+            if let Some(next) = self.read_next(slot)? {
+                merging.push((next, slot));
+            }
+        }
+        writer.flush()?;
+
+        self.add_run(path, count, level)
+    }
+}
+
+impl<T> Drop for ExternalHeap<T> {
+    fn drop(&mut self) {
+        self.runs.clear();
+        if let Some(directory) = &self.directory {
+            let _ = fs::remove_dir_all(directory);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{ExternalHeap, Record};
+
+    #[test]
+    fn test_records() {
+        let mut bytes = vec![0; <(i32, u64)>::SIZE];
+        (-5i32, 1u64 << 40).encode(&mut bytes);
+
+        assert_eq!(bytes.len(), 12);
+        assert_eq!(<(i32, u64)>::decode(&bytes), (-5, 1 << 40));
+    }
+
+    #[test]
+    fn test_heap() {
+        let parent = std::env::temp_dir().join(format!("algods-test-{}", std::process::id()));
+        let mut rng = StdRng::seed_from_u64(9);
+
+        {
+            // Buffers hold 64 elements, so the elements go through several levels of runs
+            let mut heap = ExternalHeap::<i64>::with_directory(1_024, &parent);
+            let mut expected = BinaryHeap::new();
+
+            for _ in 0..20_000 {
+                if rng.gen_bool(0.7) {
+                    let element = rng.gen_range(-1_000..1_000);
+                    heap.push(element).unwrap();
+                    expected.push(Reverse(element));
+                } else {
+                    assert_eq!(heap.pop().unwrap(), expected.pop().map(|Reverse(x)| x));
+                }
+                assert_eq!(heap.peek(), expected.peek().map(|Reverse(x)| x));
+                assert_eq!(heap.size(), expected.len());
+            }
+
+            let stats = heap.stats();
+            assert!(stats.runs_written > 10);
+            assert!(heap.runs() > 1);
+            assert!(stats.bytes_read <= stats.bytes_written);
+
+            while let Some(Reverse(element)) = expected.pop() {
+                assert_eq!(heap.pop().unwrap(), Some(element));
+            }
+            assert_eq!(heap.pop().unwrap(), None);
+            assert_eq!(heap.runs(), 0);
+            assert_eq!(heap.stats().bytes_read, heap.stats().bytes_written);
+        }
+
+        // Dropping the heap removes its run directory
+        assert_eq!(std::fs::read_dir(&parent).unwrap().count(), 0);
+        std::fs::remove_dir(&parent).unwrap();
+    }
+
+    #[test]
+    fn test_poisoned() {
+        let parent = std::env::temp_dir().join(format!("algods-poison-{}", std::process::id()));
+        let mut heap = ExternalHeap::<i64>::with_directory(1_024, &parent);
+
+        for i in 0..100 {
+            heap.push(i).unwrap();
+        }
+        assert_eq!(heap.runs(), 1);
+
+        // The open run stays readable, but its file can no longer be removed once exhausted
+        std::fs::remove_dir_all(&parent).unwrap();
+
+        // Popping the last of the 64 spilled elements finds the run exhausted
+        let failed = std::iter::from_fn(|| heap.pop().transpose()).position(|x| x.is_err());
+        assert_eq!(failed, Some(63));
+        assert!(heap.is_poisoned());
+        assert!(heap.pop().is_err());
+        assert!(heap.push(0).is_err());
+    }
+}
@@ -0,0 +1,561 @@
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+
+use super::handle::{Handle, Slab};
+use super::{AddressableHeap, Heap, MergeableHeap};
+
+type Link<T> = NonNull<Node<T>>;
+
+/// Nodes are allocated one by one and link to each other by pointer. Siblings form a
+/// circular doubly linked list, both in the root list and in child lists.
+struct Node<T> {
+    element: T,
+    parent: Option<Link<T>>,
+    child: Option<Link<T>>,
+    left: Link<T>,
+    right: Link<T>,
+    degree: usize,
+    marked: bool,
+    /// Handle the element was inserted with, if any
+    handle: Option<Handle>,
+}
+
+/// Fibonacci heap with every node allocated on its own, linked by pointer.
+///
+/// Pushes, melds and decrease-keys take O(1) amortized time and pops O(log n) amortized.
+/// Melding only splices the root lists, so the nodes of the other heap are never touched.
+pub struct FibonacciHeap<T> {
+    min: Option<Link<T>>,
+    size: usize,
+    /// Node of every element inserted with a handle
+    handles: Slab<Link<T>>,
+    /// The heap owns its nodes, which are allocated as `Box`es
+    marker: PhantomData<Box<Node<T>>>,
+}
+
+// Nodes are only reachable through the heap owning them, like the contents of a `Box`
+unsafe impl<T: Send> Send for FibonacciHeap<T> {}
+unsafe impl<T: Sync> Sync for FibonacciHeap<T> {}
+
+impl<T> FibonacciHeap<T> {
+    fn node(&self, link: Link<T>) -> &Node<T> {
+        // SAFETY: every link held by the heap points to a node it allocated and has not
+        // freed yet, and the shared borrow of the heap keeps the node from being changed
+        unsafe { link.as_ref() }
+    }
+
+    fn node_mut(&mut self, mut link: Link<T>) -> &mut Node<T> {
+        // SAFETY: as for `node`, with the exclusive borrow of the heap ruling out any
+        // other reference to the node
+        unsafe { link.as_mut() }
+    }
+
+    fn element(&self, link: Link<T>) -> &T {
+        &self.node(link).element
+    }
+
+    /// Allocate a node forming a singleton list
+    fn alloc(&mut self, element: T) -> Link<T> {
+        let link = NonNull::from(Box::leak(Box::new(Node {
+            element,
+            parent: None,
+            child: None,
+            left: NonNull::dangling(),
+            right: NonNull::dangling(),
+            degree: 0,
+            marked: false,
+            handle: None,
+        })));
+
+        let node = self.node_mut(link);
+        node.left = link;
+        node.right = link;
+        self.size += 1;
+        link
+    }
+
+    /// Free a node that is no longer linked from anywhere, releasing its handle if this
+    /// heap issued it
+    fn free(&mut self, link: Link<T>) -> T {
+        // SAFETY: the node was allocated by `alloc` and nothing links to it anymore
+        let node = unsafe { Box::from_raw(link.as_ptr()) };
+        self.size -= 1;
+
+        if let Some(slot) = node.handle.and_then(|handle| self.handles.resolve(handle)) {
+            self.handles.remove(slot);
+        }
+        node.element
+    }
+
+    fn siblings(&self, start: Link<T>) -> Vec<Link<T>> {
+        let mut result = vec![start];
+        let mut current = self.node(start).right;
+
+        while current != start {
+            result.push(current);
+            current = self.node(current).right;
+        }
+
+        result
+    }
+
+    /// Free all nodes, collecting their elements in arbitrary order
+    fn take_elements(&mut self) -> Vec<T> {
+        let mut elements = Vec::with_capacity(self.size);
+        let mut lists: Vec<_> = self.min.take().into_iter().collect();
+
+        while let Some(start) = lists.pop() {
+            for link in self.siblings(start) {
+                lists.extend(self.node(link).child);
+                elements.push(self.free(link));
+            }
+        }
+
+        elements
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        FibonacciHeap {
+            min: None,
+            size: 0,
+            handles: Slab::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Join two circular lists, placing the list starting at `b` right after `a`
+    fn splice(&mut self, a: Link<T>, b: Link<T>) {
+        let a_right = self.node(a).right;
+        let b_left = self.node(b).left;
+
+        self.node_mut(a).right = b;
+        self.node_mut(b).left = a;
+        self.node_mut(a_right).left = b_left;
+        self.node_mut(b_left).right = a_right;
+    }
+
+    /// Remove a node from the circular list it is in, leaving it as a singleton
+    fn unlink(&mut self, link: Link<T>) {
+        let left = self.node(link).left;
+        let right = self.node(link).right;
+
+        self.node_mut(left).right = right;
+        self.node_mut(right).left = left;
+        self.node_mut(link).left = link;
+        self.node_mut(link).right = link;
+    }
+
+    fn add_root(&mut self, link: Link<T>) {
+        self.node_mut(link).parent = None;
+        self.node_mut(link).marked = false;
+
+        match self.min {
+            None => self.min = Some(link),
+            Some(min) => {
+                self.splice(min, link);
+                if self.element(link) < self.element(min) {
+                    self.min = Some(link);
+                }
+            }
+        }
+    }
+
+    /// Make `child` a child of `parent`, both of them being roots
+    fn link(&mut self, child: Link<T>, parent: Link<T>) {
+        self.unlink(child);
+        self.node_mut(child).parent = Some(parent);
+        self.node_mut(child).marked = false;
+
+        match self.node(parent).child {
+            None => self.node_mut(parent).child = Some(child),
+            Some(first) => self.splice(first, child),
+        }
+        self.node_mut(parent).degree += 1;
+    }
+
+    /// Link roots of equal degree until all roots have distinct degrees
+    fn consolidate(&mut self, start: Link<T>) {
+        let mut by_degree: Vec<Option<Link<T>>> = Vec::new();
+
+        for root in self.siblings(start) {
+            let mut x = root;
+            let mut degree = self.node(x).degree;
+
+            loop {
+                if degree >= by_degree.len() {
+                    by_degree.resize(degree + 1, None);
+                }
+
+                match by_degree[degree].take() {
+                    None => break,
+                    Some(mut y) => {
+                        if self.element(y) < self.element(x) {
+                            std::mem::swap(&mut x, &mut y);
+                        }
+                        self.link(y, x);
+                        degree += 1;
+                    }
+                }
+            }
+
+            by_degree[degree] = Some(x);
+        }
+
+        self.min = None;
+        for root in by_degree.into_iter().flatten() {
+            match self.min {
+                Some(min) if self.element(min) <= self.element(root) => {}
+                _ => self.min = Some(root),
+            }
+        }
+    }
+
+    /// Move a node from the child list of its parent into the root list
+    fn cut(&mut self, link: Link<T>, parent: Link<T>) {
+        if self.node(link).right == link {
+            self.node_mut(parent).child = None;
+        } else {
+            if self.node(parent).child == Some(link) {
+                self.node_mut(parent).child = Some(self.node(link).right);
+            }
+            self.unlink(link);
+        }
+        self.node_mut(parent).degree -= 1;
+
+        self.add_root(link);
+    }
+
+    fn cascading_cut(&mut self, link: Link<T>) {
+        let mut current = link;
+
+        while let Some(parent) = self.node(current).parent {
+            if !self.node(current).marked {
+                self.node_mut(current).marked = true;
+                break;
+            }
+
+            self.cut(current, parent);
+            current = parent;
+        }
+    }
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for FibonacciHeap<T> {
+    fn drop(&mut self) {
+        self.take_elements();
+    }
+}
+
+impl<T: Ord> Heap<T> for FibonacciHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.min.map(|link| self.element(link))
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let min = self.min?;
+
+        // Promote all children of the minimum to roots
+        if let Some(child) = self.node_mut(min).child.take() {
+            for link in self.siblings(child) {
+                let node = self.node_mut(link);
+                node.parent = None;
+                node.marked = false;
+            }
+            self.splice(min, child);
+            self.node_mut(min).degree = 0;
+        }
+
+        let next = self.node(min).right;
+        self.unlink(min);
+
+        if next == min {
+            self.min = None;
+        } else {
+            self.consolidate(next);
+        }
+
+        Some(self.free(min))
+    }
+
+    fn push(&mut self, element: T) {
+        let link = self.alloc(element);
+        self.add_root(link);
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        let mut stack = self.min.map_or_else(Vec::new, |min| self.siblings(min));
+
+        Box::new(std::iter::from_fn(move || {
+            let node = self.node(stack.pop()?);
+            if let Some(child) = node.child {
+                stack.extend(self.siblings(child));
+            }
+            Some(&node.element)
+        }))
+    }
+
+    fn clear(&mut self) {
+        self.take_elements();
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.take_elements().into_iter())
+    }
+}
+
+impl<T: Ord> IntoIterator for FibonacciHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the trees
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.take_elements().into_iter()
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for FibonacciHeap<T> {
+    /// Root lists are spliced in O(1), the nodes of `other` staying where they are.
+    /// Handles of `self` stay valid, handles of `other` are consumed along with it.
+    fn meld(&mut self, mut other: Self) {
+        self.size += std::mem::take(&mut other.size);
+
+        if let Some(other_min) = other.min.take() {
+            match self.min {
+                None => self.min = Some(other_min),
+                Some(min) => {
+                    self.splice(min, other_min);
+                    if self.element(other_min) < self.element(min) {
+                        self.min = Some(other_min);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Ord> AddressableHeap<T> for FibonacciHeap<T> {
+    fn insert(&mut self, element: T) -> Handle {
+        let link = self.alloc(element);
+        let handle = self.handles.insert(link);
+        self.node_mut(link).handle = Some(handle);
+        self.add_root(link);
+
+        handle
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.handles
+            .resolve(handle)
+            .map(|slot| self.element(self.handles[slot]))
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) {
+        let slot = self.handles.resolve(handle).expect("Invalid heap handle");
+        let link = self.handles[slot];
+        assert!(
+            element <= *self.element(link),
+            "New key is greater than the current key"
+        );
+        self.node_mut(link).element = element;
+
+        if let Some(parent) = self.node(link).parent {
+            if self.element(link) < self.element(parent) {
+                self.cut(link, parent);
+                self.cascading_cut(parent);
+            }
+        }
+
+        if let Some(min) = self.min {
+            if self.element(link) < self.element(min) {
+                self.min = Some(link);
+            }
+        }
+    }
+
+    fn delete(&mut self, handle: Handle) -> Option<T> {
+        let link = self.handles[self.handles.resolve(handle)?];
+
+        // Cut the node out as if its key was decreased to minus infinity
+        if let Some(parent) = self.node(link).parent {
+            self.cut(link, parent);
+            self.cascading_cut(parent);
+        }
+        self.min = Some(link);
+
+        self.pop()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ds::heap::{AddressableHeap, Heap, MergeableHeap};
+
+    use super::FibonacciHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = FibonacciHeap::<i32>::new();
+
+        heap.push(1);
+        heap.push(2);
+        heap.push(3);
+        heap.push(4);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), None);
+
+        heap.push(4);
+        heap.push(1);
+        heap.push(3);
+        heap.push(2);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_heap() {
+        let mut heap: FibonacciHeap<i32> = FibonacciHeap::new();
+
+        // Test inserting elements
+        for i in 1..=100 {
+            heap.push(i);
+        }
+
+        // Test the smallest element in the heap
+        assert_eq!(heap.peek(), Some(&1));
+
+        // Test inserting smaller elements after creating the heap
+        for i in -100..=0 {
+            heap.push(i);
+        }
+
+        // Test the smallest element in the heap
+        assert_eq!(heap.peek(), Some(&-100));
+        assert_eq!(heap.size(), 201);
+
+        // Test popping all elements from the heap
+        for i in -100..=100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+
+        // Test heap is empty
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_delete() {
+        let mut heap = FibonacciHeap::<i32>::new();
+
+        let handles: Vec<_> = (0..20).map(|i| heap.insert(i)).collect();
+        assert_eq!(heap.pop(), Some(0));
+
+        assert_eq!(heap.delete(handles[1]), Some(1));
+        assert_eq!(heap.delete(handles[7]), Some(7));
+        assert_eq!(heap.delete(handles[7]), None);
+        assert_eq!(heap.delete(handles[0]), None);
+
+        for i in (2..20).filter(|&i| i != 7) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = FibonacciHeap::<i32>::new();
+
+        let handles: Vec<_> = (0..50).map(|i| heap.insert(100 + i)).collect();
+
+        // Popping once consolidates the trees, so later decreases have to cut nodes
+        assert_eq!(heap.pop(), Some(100));
+
+        heap.decrease_key(handles[30], 5);
+        heap.decrease_key(handles[40], 1);
+        heap.decrease_key(handles[20], 3);
+        assert_eq!(heap.get(handles[20]), Some(&3));
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.pop(), Some(101));
+
+        // Popped elements leave stale handles behind
+        assert_eq!(heap.get(handles[40]), None);
+        assert_eq!(heap.get(handles[0]), None);
+        assert_eq!(heap.size(), 45);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = FibonacciHeap::<i32>::new();
+        let mut other = FibonacciHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+        other.pop();
+
+        let handle = heap.insert(200);
+        heap.meld(other);
+        heap.decrease_key(handle, -1);
+
+        assert_eq!(heap.size(), 100);
+        assert_eq!(heap.pop(), Some(-1));
+        assert_eq!(heap.pop(), Some(0));
+        for i in 2..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld_handles() {
+        let mut heap = FibonacciHeap::<i32>::new();
+        let mut other = FibonacciHeap::<i32>::new();
+
+        let handles: Vec<_> = (10..20).map(|i| heap.insert(i)).collect();
+        for i in 0..10 {
+            other.insert(i);
+        }
+        heap.meld(other);
+
+        // Popping the melded elements leaves the handles of this heap alone
+        for i in 0..10 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        heap.decrease_key(handles[9], 1);
+        assert_eq!(heap.delete(handles[5]), Some(15));
+
+        assert_eq!(heap.pop(), Some(1));
+        for i in (10..19).filter(|&i| i != 15) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+}
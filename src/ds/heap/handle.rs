@@ -0,0 +1,214 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Stable reference to an element stored in an addressable heap.
+///
+/// A handle stays valid until its element leaves the heap. Stale handles are
+/// detected through the generation counter of the slot they point to, and handles
+/// of another heap through the id of the slab that issued them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    slab: usize,
+    index: usize,
+    generation: usize,
+}
+
+impl Handle {
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+}
+
+struct Entry<N> {
+    generation: usize,
+    value: Option<N>,
+}
+
+/// Id of the next slab to be created, so that no two slabs issue the same handles
+static NEXT_SLAB_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Vector of slots with a free list, handing out generation-checked handles
+pub(crate) struct Slab<N> {
+    id: usize,
+    entries: Vec<Entry<N>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<N> Slab<N> {
+    pub(crate) fn new() -> Self {
+        Slab {
+            id: NEXT_SLAB_ID.fetch_add(1, Ordering::Relaxed),
+            entries: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Store a value and return the handle of the slot it was put in
+    pub(crate) fn insert(&mut self, value: N) -> Handle {
+        self.len += 1;
+
+        match self.free.pop() {
+            Some(index) => {
+                let entry = &mut self.entries[index];
+                entry.value = Some(value);
+                Handle {
+                    slab: self.id,
+                    index,
+                    generation: entry.generation,
+                }
+            }
+            None => {
+                self.entries.push(Entry {
+                    generation: 0,
+                    value: Some(value),
+                });
+                Handle {
+                    slab: self.id,
+                    index: self.entries.len() - 1,
+                    generation: 0,
+                }
+            }
+        }
+    }
+
+    /// Take the value out of a slot, invalidating all handles pointing to it
+    pub(crate) fn remove(&mut self, index: usize) -> N {
+        let entry = &mut self.entries[index];
+        let value = entry.value.take().expect("Slab slot is vacant");
+
+        entry.generation += 1;
+        self.free.push(index);
+        self.len -= 1;
+
+        value
+    }
+
+    /// Resolve a handle into a slot index, if this slab issued it and it is still valid
+    pub(crate) fn resolve(&self, handle: Handle) -> Option<usize> {
+        if handle.slab != self.id {
+            return None;
+        }
+
+        self.entries
+            .get(handle.index)
+            .filter(|entry| entry.generation == handle.generation && entry.value.is_some())
+            .map(|_| handle.index)
+    }
+
+    /// Access the value of a slot, if it is occupied
+    pub(crate) fn get(&self, index: usize) -> Option<&N> {
+        self.entries
+            .get(index)
+            .and_then(|entry| entry.value.as_ref())
+    }
+
+    /// Mutable access to two distinct occupied slots at once
+    pub(crate) fn pair_mut(&mut self, a: usize, b: usize) -> (&mut N, &mut N) {
+        let [first, second] = self
+            .entries
+            .get_disjoint_mut([a, b])
+            .expect("Slab slots must be distinct");
+
+        (
+            first.value.as_mut().expect("Slab slot is vacant"),
+            second.value.as_mut().expect("Slab slot is vacant"),
+        )
+    }
+
+    /// Move all slots of another slab to the end of this one.
+    ///
+    /// Moved slots keep their relative order, so their indices grow by the value of
+    /// `slots()` taken before the call. The `relocate` callback is invoked on every
+    /// moved value so that it can fix up the indices it stores. Handles issued by
+    /// `other` do not resolve in this slab.
+    pub(crate) fn append<F>(&mut self, other: Slab<N>, mut relocate: F)
+    where
+        F: FnMut(&mut N),
+    {
+        let offset = self.entries.len();
+
+        for mut entry in other.entries {
+            if let Some(value) = entry.value.as_mut() {
+                relocate(value);
+            }
+            self.entries.push(entry);
+        }
+        self.free
+            .extend(other.free.into_iter().map(|index| index + offset));
+        self.len += other.len;
+    }
+
+    /// Remove all values, invalidating every outstanding handle
+    pub(crate) fn clear(&mut self) {
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if entry.value.take().is_some() {
+                entry.generation += 1;
+                self.free.push(index);
+            }
+        }
+        self.len = 0;
+    }
+
+    /// Remove all values like `clear`, returning them in slot order
+    pub(crate) fn drain(&mut self) -> Vec<N> {
+        let mut values = Vec::with_capacity(self.len);
+
+        for (index, entry) in self.entries.iter_mut().enumerate() {
+            if let Some(value) = entry.value.take() {
+                entry.generation += 1;
+                self.free.push(index);
+                values.push(value);
+            }
+        }
+        self.len = 0;
+
+        values
+    }
+
+    /// Iterate over the values of occupied slots in slot order
+    pub(crate) fn values(&self) -> impl Iterator<Item = &N> {
+        self.entries.iter().filter_map(|entry| entry.value.as_ref())
+    }
+
+    /// Consume the slab, yielding the values of occupied slots in slot order
+    pub(crate) fn into_values(self) -> impl Iterator<Item = N> {
+        self.entries.into_iter().filter_map(|entry| entry.value)
+    }
+
+    /// Number of occupied slots
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Number of slots, including vacant ones
+    pub(crate) fn slots(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+impl<N> Default for Slab<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N> std::ops::Index<usize> for Slab<N> {
+    type Output = N;
+
+    fn index(&self, index: usize) -> &N {
+        self.entries[index]
+            .value
+            .as_ref()
+            .expect("Slab slot is vacant")
+    }
+}
+
+impl<N> std::ops::IndexMut<usize> for Slab<N> {
+    fn index_mut(&mut self, index: usize) -> &mut N {
+        self.entries[index]
+            .value
+            .as_mut()
+            .expect("Slab slot is vacant")
+    }
+}
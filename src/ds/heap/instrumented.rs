@@ -0,0 +1,302 @@
+use std::cell::Cell;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Sub};
+
+use super::Heap;
+
+thread_local! {
+    static COUNTS: Cell<OperationCounts> = const {
+        Cell::new(OperationCounts {
+            comparisons: 0,
+            clones: 0,
+        })
+    };
+}
+
+/// Number of comparisons and clones of [`Counted`] elements
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperationCounts {
+    pub comparisons: u64,
+    pub clones: u64,
+}
+
+impl OperationCounts {
+    /// Operations made so far on the current thread
+    pub fn current() -> Self {
+        COUNTS.with(Cell::get)
+    }
+
+    fn record(update: impl FnOnce(&mut OperationCounts)) {
+        COUNTS.with(|counts| {
+            let mut current = counts.get();
+            update(&mut current);
+            counts.set(current);
+        });
+    }
+}
+
+impl Add for OperationCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        OperationCounts {
+            comparisons: self.comparisons + other.comparisons,
+            clones: self.clones + other.clones,
+        }
+    }
+}
+
+impl AddAssign for OperationCounts {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sub for OperationCounts {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        OperationCounts {
+            comparisons: self.comparisons - other.comparisons,
+            clones: self.clones - other.clones,
+        }
+    }
+}
+
+/// Run a function and count the operations it makes on the current thread
+pub fn count_operations<R>(f: impl FnOnce() -> R) -> (R, OperationCounts) {
+    let before = OperationCounts::current();
+    let result = f();
+    (result, OperationCounts::current() - before)
+}
+
+/// Run a function without counting the operations it makes, such as checks that are not
+/// part of the algorithm being measured
+pub(crate) fn uncounted<R>(f: impl FnOnce() -> R) -> R {
+    let before = OperationCounts::current();
+    let result = f();
+    COUNTS.with(|counts| counts.set(before));
+    result
+}
+
+/// Element wrapper counting comparisons and clones in a thread-local counter.
+///
+/// Every call to `cmp`, `partial_cmp` or `eq` counts as one comparison. Hashing does not
+/// compare elements and goes uncounted.
+#[derive(Debug, Default)]
+pub struct Counted<T>(pub T);
+
+impl<T> Counted<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Clone> Clone for Counted<T> {
+    fn clone(&self) -> Self {
+        OperationCounts::record(|counts| counts.clones += 1);
+        Counted(self.0.clone())
+    }
+}
+
+/// Copies go uncounted, so that keys of heaps requiring `Copy`, such as radix heaps,
+/// can be counted as well. Only their comparisons show up.
+impl<T: Copy> Copy for Counted<T> {}
+
+impl<T: PartialEq> PartialEq for Counted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        OperationCounts::record(|counts| counts.comparisons += 1);
+        self.0 == other.0
+    }
+}
+
+impl<T: Eq> Eq for Counted<T> {}
+
+impl<T: Hash> Hash for Counted<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+impl<T: Ord> PartialOrd for Counted<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord> Ord for Counted<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        OperationCounts::record(|counts| counts.comparisons += 1);
+        self.0.cmp(&other.0)
+    }
+}
+
+/// Number of calls of a heap operation and the operations they made in total
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct OperationStats {
+    pub calls: u64,
+    pub counts: OperationCounts,
+}
+
+impl OperationStats {
+    fn record(&mut self, counts: OperationCounts) {
+        self.calls += 1;
+        self.counts += counts;
+    }
+}
+
+/// Operation statistics of an [`InstrumentedHeap`], by heap operation
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    pub push: OperationStats,
+    pub pop: OperationStats,
+    pub peek: OperationStats,
+}
+
+/// Decorator recording the comparisons and clones every operation of a heap makes.
+///
+/// Counts come from [`Counted`] elements, so a heap of other elements records calls
+/// with no operations.
+pub struct InstrumentedHeap<H> {
+    heap: H,
+    // Peeking takes &self
+    stats: Cell<HeapStats>,
+}
+
+impl<H> InstrumentedHeap<H> {
+    pub fn new(heap: H) -> Self {
+        InstrumentedHeap {
+            heap,
+            stats: Cell::new(HeapStats::default()),
+        }
+    }
+
+    pub fn stats(&self) -> HeapStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(HeapStats::default());
+    }
+
+    pub fn into_inner(self) -> H {
+        self.heap
+    }
+
+    fn record(
+        &self,
+        operation: fn(&mut HeapStats) -> &mut OperationStats,
+        counts: OperationCounts,
+    ) {
+        let mut stats = self.stats.get();
+        operation(&mut stats).record(counts);
+        self.stats.set(stats);
+    }
+}
+
+impl<H: Default> Default for InstrumentedHeap<H> {
+    fn default() -> Self {
+        Self::new(H::default())
+    }
+}
+
+impl<T, H: Heap<T>> Heap<T> for InstrumentedHeap<H> {
+    fn peek(&self) -> Option<&T> {
+        let (element, counts) = count_operations(|| self.heap.peek());
+        self.record(|stats| &mut stats.peek, counts);
+        element
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let (element, counts) = count_operations(|| self.heap.pop());
+        self.record(|stats| &mut stats.pop, counts);
+        element
+    }
+
+    fn push(&mut self, element: T) {
+        let ((), counts) = count_operations(|| self.heap.push(element));
+        self.record(|stats| &mut stats.push, counts);
+    }
+
+    fn size(&self) -> usize {
+        self.heap.size()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        self.heap.iter()
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ds::heap::binary_heap_vec::BinaryHeapVec;
+    use crate::ds::heap::Heap;
+
+    use super::{count_operations, uncounted, Counted, InstrumentedHeap, OperationCounts};
+
+    #[test]
+    fn test_counted() {
+        let ((), counts) = count_operations(|| {
+            let x = Counted(String::from("a"));
+            let y = x.clone();
+            assert!(x <= y);
+            assert!(x == y);
+            assert_eq!(x.cmp(&Counted(String::from("b"))), std::cmp::Ordering::Less);
+
+            // Copies of copyable elements are not clones
+            let z = Counted(1);
+            let w = z;
+            assert!(z == w);
+        });
+
+        assert_eq!(
+            counts,
+            OperationCounts {
+                comparisons: 4,
+                clones: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_uncounted() {
+        let ((), counts) = count_operations(|| {
+            assert!(Counted(1) < Counted(2));
+            uncounted(|| assert!(Counted(1) < Counted(2)));
+        });
+
+        assert_eq!(counts.comparisons, 1);
+    }
+
+    #[test]
+    fn test_instrumented_heap() {
+        let mut heap = InstrumentedHeap::new(BinaryHeapVec::new());
+
+        // Pushing in descending order sifts every element up to the root
+        for i in (0..1_024).rev() {
+            heap.push(Counted(i));
+        }
+        assert_eq!(heap.peek().map(|x| x.0), Some(0));
+        while heap.pop().is_some() {}
+
+        let stats = heap.stats();
+        assert_eq!(stats.push.calls, 1_024);
+        assert_eq!(stats.pop.calls, 1_025);
+        assert_eq!(stats.peek.calls, 1);
+        assert_eq!(stats.peek.counts, OperationCounts::default());
+        assert_eq!(stats.push.counts.clones, 0);
+
+        // One comparison per level for pushes, at most two per level for pops
+        let pushes: u64 = (1..=1_024u64).map(|i| i.ilog2() as u64).sum();
+        assert_eq!(stats.push.counts.comparisons, pushes);
+        assert!(stats.pop.counts.comparisons <= 2 * 10 * 1_024);
+
+        heap.reset_stats();
+        assert_eq!(heap.stats().push.calls, 0);
+    }
+}
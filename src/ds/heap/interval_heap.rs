@@ -0,0 +1,310 @@
+use super::{DoubleEndedHeap, Heap};
+
+/// Double-ended heap stored in a vector as a complete binary tree of intervals.
+///
+/// Node `k` holds the pair `data[2k] <= data[2k + 1]` and its interval contains the
+/// intervals of both its children, so the root holds the minimum and the maximum.
+/// The last node may hold a single element.
+pub struct IntervalHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> IntervalHeap<T> {
+    pub fn new() -> Self {
+        IntervalHeap { data: vec![] }
+    }
+
+    /// Move the low end of an interval up while it is smaller than its parent's low end
+    fn bubble_up_min(&mut self, index: usize) {
+        let mut index = index;
+
+        while index >= 2 {
+            let parent = ((index / 2 - 1) / 2) * 2;
+            if self.data[index] >= self.data[parent] {
+                break;
+            }
+
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Move the high end of an interval up while it is larger than its parent's high end
+    fn bubble_up_max(&mut self, index: usize) {
+        let mut index = index;
+
+        while index >= 2 {
+            let parent = ((index / 2 - 1) / 2) * 2 + 1;
+            if self.data[index] <= self.data[parent] {
+                break;
+            }
+
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    /// Move an element from the low end of a node down along the low ends of its descendants
+    fn trickle_down_min(&mut self, index: usize) {
+        let count = self.data.len();
+        let mut index = index;
+
+        loop {
+            // Keep the interval of the current node ordered
+            if index + 1 < count && self.data[index] > self.data[index + 1] {
+                self.data.swap(index, index + 1);
+            }
+
+            let first_child = 2 * index + 2;
+            if first_child >= count {
+                break;
+            }
+
+            let second_child = first_child + 2;
+            let child = if second_child < count && self.data[second_child] < self.data[first_child]
+            {
+                second_child
+            } else {
+                first_child
+            };
+
+            if self.data[child] >= self.data[index] {
+                break;
+            }
+
+            self.data.swap(index, child);
+            index = child;
+        }
+    }
+
+    /// Move an element from the high end of a node down along the high ends of its descendants
+    fn trickle_down_max(&mut self, index: usize) {
+        let count = self.data.len();
+        let mut index = index;
+
+        loop {
+            // Keep the interval of the current node ordered
+            if self.data[index - 1] > self.data[index] {
+                self.data.swap(index - 1, index);
+            }
+
+            // High end of a child, or its only element when it is the last single-element node
+            let high_end = |child: usize| (child + 1).min(count - 1);
+
+            let first_child = 2 * index;
+            if first_child >= count {
+                break;
+            }
+
+            let second_child = first_child + 2;
+            let child = if second_child < count
+                && self.data[high_end(second_child)] > self.data[high_end(first_child)]
+            {
+                high_end(second_child)
+            } else {
+                high_end(first_child)
+            };
+
+            if self.data[child] <= self.data[index] {
+                break;
+            }
+
+            self.data.swap(index, child);
+
+            // A single-element node is always the last one, so there is nothing below it
+            if child % 2 == 0 {
+                break;
+            }
+            index = child;
+        }
+    }
+}
+
+impl<T: Ord> Default for IntervalHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> DoubleEndedHeap<T> for IntervalHeap<T> {
+    fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn peek_max(&self) -> Option<&T> {
+        self.data.get(1).or(self.data.first())
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let removed_value = self.data.swap_remove(0);
+        if !self.data.is_empty() {
+            self.trickle_down_min(0);
+        }
+
+        Some(removed_value)
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        if self.data.len() <= 2 {
+            return self.data.pop();
+        }
+
+        let removed_value = self.data.swap_remove(1);
+        self.trickle_down_max(1);
+
+        Some(removed_value)
+    }
+}
+
+impl<T: Ord> Heap<T> for IntervalHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.peek_min()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    fn push(&mut self, element: T) {
+        self.data.push(element);
+        let index = self.data.len() - 1;
+
+        if index % 2 == 1 {
+            // Completed a pair, order it and move whichever end changed
+            if self.data[index] < self.data[index - 1] {
+                self.data.swap(index - 1, index);
+                self.bubble_up_min(index - 1);
+            } else {
+                self.bubble_up_max(index);
+            }
+        } else if index > 0 {
+            // A new single-element node, check it against the interval of its parent
+            let parent = ((index / 2 - 1) / 2) * 2;
+            if self.data[index] < self.data[parent] {
+                self.bubble_up_min(index);
+            } else if self.data[index] > self.data[parent + 1] {
+                self.bubble_up_max(index);
+            }
+        }
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.data.drain(..))
+    }
+}
+
+impl<T: Ord> IntoIterator for IntervalHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::{DoubleEndedHeap, Heap};
+
+    use super::IntervalHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = IntervalHeap::<i32>::new();
+
+        heap.push(3);
+        heap.push(1);
+        heap.push(4);
+        heap.push(2);
+
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&4));
+
+        assert_eq!(heap.pop_max(), Some(4));
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(3));
+        assert_eq!(heap.pop_min(), Some(2));
+        assert_eq!(heap.pop_max(), None);
+        assert_eq!(heap.pop_min(), None);
+    }
+
+    #[test]
+    fn test_heap() {
+        let mut heap: IntervalHeap<i32> = IntervalHeap::new();
+
+        // Test inserting elements
+        for i in 1..=100 {
+            heap.push(i);
+        }
+
+        // Test inserting smaller elements after creating the heap
+        for i in -100..=0 {
+            heap.push(i);
+        }
+
+        // Test both extremes of the heap
+        assert_eq!(heap.peek(), Some(&-100));
+        assert_eq!(heap.peek_max(), Some(&100));
+
+        // Test popping all elements from the heap, alternating between both ends
+        for i in 0..=100 {
+            assert_eq!(heap.pop_min(), Some(i - 100));
+            if i < 100 {
+                assert_eq!(heap.pop_max(), Some(100 - i));
+            }
+        }
+
+        // Test heap is empty
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut heap = IntervalHeap::<i32>::new();
+        let mut sorted = Vec::new();
+
+        for _ in 0..5_000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let value = rng.gen_range(0..100);
+                    heap.push(value);
+                    let position = sorted.partition_point(|&x| x < value);
+                    sorted.insert(position, value);
+                }
+                2 => assert_eq!(
+                    heap.pop_min(),
+                    (!sorted.is_empty()).then(|| sorted.remove(0))
+                ),
+                _ => assert_eq!(heap.pop_max(), sorted.pop()),
+            }
+
+            assert_eq!(heap.peek_min(), sorted.first());
+            assert_eq!(heap.peek_max(), sorted.last());
+        }
+    }
+}
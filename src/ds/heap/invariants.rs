@@ -0,0 +1,84 @@
+use std::fmt;
+
+use super::instrumented;
+use super::HeapInvariants;
+
+/// Structural property of a heap found broken by [`HeapInvariants::check_invariants`].
+///
+/// Positions are the indices a heap uses internally for its elements, such as vector
+/// positions, or the order in which a walk down from the roots reaches tree nodes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InvariantViolation {
+    /// A child comes before its parent in the heap's order
+    HeapOrder { parent: usize, child: usize },
+    /// A node does not point back to the node holding it as a child
+    ParentLink { node: usize },
+    /// The rank stored in a node differs from the one computed from its children
+    Rank {
+        node: usize,
+        stored: usize,
+        expected: usize,
+    },
+    /// The left child of a node has a smaller rank than its right child
+    Leftist { node: usize },
+    /// The children of a node are not binomial trees of orders 0 to k - 1
+    TreeShape { node: usize },
+    /// The roots are not listed by strictly increasing order, the duplicate order given
+    /// when two trees share one
+    RootOrder { order: usize },
+    /// A handle slot does not point to the position of its element
+    Handle { slot: usize },
+    /// The number of elements reachable from the roots differs from the stored count
+    Size { reachable: usize, expected: usize },
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvariantViolation::HeapOrder { parent, child } => {
+                write!(f, "element at {child} comes before its parent at {parent}")
+            }
+            InvariantViolation::ParentLink { node } => {
+                write!(f, "node {node} has a wrong parent link")
+            }
+            InvariantViolation::Rank {
+                node,
+                stored,
+                expected,
+            } => write!(f, "node {node} has rank {stored} instead of {expected}"),
+            InvariantViolation::Leftist { node } => {
+                write!(f, "left child of node {node} has the smaller rank")
+            }
+            InvariantViolation::TreeShape { node } => {
+                write!(f, "node {node} is not the root of a binomial tree")
+            }
+            InvariantViolation::RootOrder { order } => {
+                write!(
+                    f,
+                    "roots are not in strictly increasing order at order {order}"
+                )
+            }
+            InvariantViolation::Handle { slot } => {
+                write!(f, "handle slot {slot} does not point to its element")
+            }
+            InvariantViolation::Size {
+                reachable,
+                expected,
+            } => write!(f, "{reachable} elements are reachable out of {expected}"),
+        }
+    }
+}
+
+impl std::error::Error for InvariantViolation {}
+
+/// Check the invariants of a heap after a mutation.
+///
+/// A full check costs O(n) per operation, so it only runs in debug builds with the
+/// `check-invariants` feature enabled. Its comparisons are left out of operation counts.
+pub(crate) fn debug_check<H: HeapInvariants>(heap: &H) {
+    if cfg!(all(debug_assertions, feature = "check-invariants")) {
+        if let Err(violation) = instrumented::uncounted(|| heap.check_invariants()) {
+            panic!("Heap invariant violated: {violation}");
+        }
+    }
+}
@@ -1,14 +1,20 @@
-use super::Heap;
+use std::collections::VecDeque;
 
-struct Node<T> {
+use super::compare::{Compare, MinOrder};
+use super::handle::{Handle, Slab};
+use super::invariants::{self, InvariantViolation};
+use super::storage::{Boxed, NodeStorage};
+use super::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
+
+pub struct Node<T, S: NodeStorage<Node<T, S>>> {
     element: T,
-    left: Option<Box<Node<T>>>,
-    right: Option<Box<Node<T>>>,
+    left: Option<S::Link>,
+    right: Option<S::Link>,
     rank: usize,
 }
 
-impl<T: Ord> Node<T> {
-    fn new(element: T) -> Node<T> {
+impl<T, S: NodeStorage<Node<T, S>>> Node<T, S> {
+    fn new(element: T) -> Self {
         Node {
             element,
             left: None,
@@ -18,93 +24,592 @@ impl<T: Ord> Node<T> {
     }
 }
 
-pub struct LeftistHeap<T> {
-    root: Option<Box<Node<T>>>,
+/// Leftist heap ordered by a comparator, whose nodes live in the storage `S`, each node
+/// being boxed by default.
+///
+/// Merging, sizing and dropping never recurse, so trees of any depth are safe: descending
+/// pushes build a left path as long as the heap.
+pub struct LeftistHeap<T, C = MinOrder, S: NodeStorage<Node<T, S>> = Boxed> {
+    root: Option<S::Link>,
+    size: usize,
+    nodes: S,
+    compare: C,
+}
+
+/// Leftist heap whose elements can be updated or removed through handles
+pub type AddressableLeftistHeap<T, C = MinOrder> = LeftistHeap<T, C, HandleNodes<T>>;
+
+/// Leftist heap keeping its nodes in a slab, linked by index
+pub type ArenaLeftistHeap<T, C = MinOrder> = LeftistHeap<T, C, ArenaNodes<T>>;
+
+/// Storage of addressable leftist heaps.
+///
+/// Nodes are kept in a slab and linked by index. Each node also links to its parent, so
+/// that a handle can cut its element out of the tree.
+pub struct HandleNodes<T> {
+    slab: Slab<Node<T, HandleNodes<T>>>,
+    /// Parent of the node in every slot of `slab`
+    parents: Vec<Option<usize>>,
+}
+
+impl<T> HandleNodes<T> {
+    /// Store a detached node and return a handle to it
+    fn insert(&mut self, node: Node<T, Self>) -> Handle {
+        let handle = self.slab.insert(node);
+        self.parents.resize(self.slab.slots(), None);
+        self.parents[handle.index()] = None;
+        handle
+    }
+}
+
+impl<T> Default for HandleNodes<T> {
+    fn default() -> Self {
+        HandleNodes {
+            slab: Slab::new(),
+            parents: Vec::new(),
+        }
+    }
 }
 
-impl<T: Ord> LeftistHeap<T> {
-    pub fn new() -> LeftistHeap<T> {
-        LeftistHeap { root: None }
+impl<T> NodeStorage<Node<T, HandleNodes<T>>> for HandleNodes<T> {
+    type Link = usize;
+
+    fn alloc(&mut self, node: Node<T, Self>) -> usize {
+        self.insert(node).index()
     }
 
-    // fn merge(&mut self, mut other: LeftistHeap<T>) {
-    //     self.root = Self::merge_nodes(self.root.take(), other.root.take());
-    // }
+    fn free(&mut self, link: usize) -> Node<T, Self> {
+        self.slab.remove(link)
+    }
 
-    fn merge_nodes(
-        left: Option<Box<Node<T>>>,
-        right: Option<Box<Node<T>>>,
-    ) -> Option<Box<Node<T>>> {
-        match (left, right) {
-            (None, right) => right,
-            (left, None) => left,
-            (Some(mut left_node), Some(mut right_node)) => {
-                if left_node.element <= right_node.element {
-                    left_node.right = Self::merge_nodes(left_node.right.take(), Some(right_node));
-                    Self::update_rank(&mut left_node);
-                    Some(left_node)
-                } else {
-                    right_node.right = Self::merge_nodes(right_node.right.take(), Some(left_node));
-                    Self::update_rank(&mut right_node);
-                    Some(right_node)
+    fn node<'a>(&'a self, link: &'a usize) -> &'a Node<T, Self> {
+        &self.slab[*link]
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut usize) -> &'a mut Node<T, Self> {
+        &mut self.slab[*link]
+    }
+
+    fn append(&mut self, other: Self, roots: &mut [usize]) {
+        let offset = self.slab.slots();
+
+        self.slab.append(other.slab, |node| {
+            node.left = node.left.map(|index| index + offset);
+            node.right = node.right.map(|index| index + offset);
+        });
+        self.parents.extend(
+            other
+                .parents
+                .into_iter()
+                .map(|parent| parent.map(|index| index + offset)),
+        );
+
+        for root in roots {
+            *root += offset;
+        }
+    }
+
+    fn set_parent(&mut self, child: &usize, parent: Option<&usize>) {
+        self.parents[*child] = parent.copied();
+    }
+
+    fn check_node(
+        &self,
+        link: &usize,
+        parent: Option<&usize>,
+        position: usize,
+    ) -> Result<(), InvariantViolation> {
+        if self.parents[*link] != parent.copied() {
+            return Err(InvariantViolation::ParentLink { node: position });
+        }
+
+        Ok(())
+    }
+
+    fn check_count(&self, reachable: usize) -> Result<(), InvariantViolation> {
+        if reachable != self.slab.len() {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected: self.slab.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Storage keeping nodes in a slab linked by index, reusing freed slots instead of
+/// allocating each node on its own
+pub struct ArenaNodes<T> {
+    slab: Slab<Node<T, ArenaNodes<T>>>,
+}
+
+impl<T> Default for ArenaNodes<T> {
+    fn default() -> Self {
+        ArenaNodes { slab: Slab::new() }
+    }
+}
+
+impl<T> NodeStorage<Node<T, ArenaNodes<T>>> for ArenaNodes<T> {
+    type Link = usize;
+
+    fn alloc(&mut self, node: Node<T, Self>) -> usize {
+        self.slab.insert(node).index()
+    }
+
+    fn free(&mut self, link: usize) -> Node<T, Self> {
+        self.slab.remove(link)
+    }
+
+    fn node<'a>(&'a self, link: &'a usize) -> &'a Node<T, Self> {
+        &self.slab[*link]
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut usize) -> &'a mut Node<T, Self> {
+        &mut self.slab[*link]
+    }
+
+    fn append(&mut self, other: Self, roots: &mut [usize]) {
+        let offset = self.slab.slots();
+
+        self.slab.append(other.slab, |node| {
+            node.left = node.left.map(|index| index + offset);
+            node.right = node.right.map(|index| index + offset);
+        });
+
+        for root in roots {
+            *root += offset;
+        }
+    }
+
+    fn check_count(&self, reachable: usize) -> Result<(), InvariantViolation> {
+        if reachable != self.slab.len() {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected: self.slab.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Ord, S: NodeStorage<Node<T, S>>> LeftistHeap<T, MinOrder, S> {
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+
+    /// Build a heap from a vector in O(n) by merging single-node trees pairwise
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(vec, MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> LeftistHeap<T, C, S> {
+    pub fn with_comparator(compare: C) -> Self {
+        LeftistHeap {
+            root: None,
+            size: 0,
+            nodes: S::default(),
+            compare,
+        }
+    }
+
+    pub fn from_vec_with_comparator(vec: Vec<T>, compare: C) -> Self {
+        let mut heap = Self::with_comparator(compare);
+        heap.extend(vec);
+        heap
+    }
+
+    /// Merge trees taken from the front of a queue in pairs, pushing each result to the back.
+    ///
+    /// Every round halves the number of trees at a cost linear in their count, so k trees
+    /// are merged in O(k) overall.
+    fn merge_queue(&mut self, mut queue: VecDeque<S::Link>) -> Option<S::Link> {
+        while queue.len() > 1 {
+            let first = queue.pop_front();
+            let second = queue.pop_front();
+            if let Some(merged) = self.merge_nodes(first, second) {
+                queue.push_back(merged);
+            }
+        }
+
+        queue.pop_front()
+    }
+
+    /// Merge two trees along their right spines.
+    ///
+    /// The spines are walked top-down, collecting the nodes that come first in order, and
+    /// then relinked bottom-up with their ranks restored, so the stack depth stays constant.
+    fn merge_nodes(&mut self, left: Option<S::Link>, right: Option<S::Link>) -> Option<S::Link> {
+        let mut spine = Vec::new();
+        let (mut left, mut right) = (left, right);
+
+        let mut merged = loop {
+            match (left, right) {
+                (None, rest) | (rest, None) => break rest,
+                (Some(first), Some(second)) => {
+                    let (mut top, other) = if self.compare.le(
+                        &self.nodes.node(&first).element,
+                        &self.nodes.node(&second).element,
+                    ) {
+                        (first, second)
+                    } else {
+                        (second, first)
+                    };
+                    left = self.nodes.node_mut(&mut top).right.take();
+                    right = Some(other);
+                    spine.push(top);
                 }
             }
+        };
+
+        for mut node in spine.into_iter().rev() {
+            self.set_right(&mut node, merged);
+            self.update_rank(&mut node);
+            merged = Some(node);
         }
+
+        merged
     }
 
-    fn update_rank(node: &mut Box<Node<T>>) {
-        let rank_left = node.left.as_ref().map_or(0, |left_node| left_node.rank + 1);
-        let rank_right = node
-            .right
-            .as_ref()
-            .map_or(0, |right_node| right_node.rank + 1);
+    fn set_right(&mut self, node: &mut S::Link, child: Option<S::Link>) {
+        if let Some(child) = &child {
+            self.nodes.set_parent(child, Some(node));
+        }
+        self.nodes.node_mut(node).right = child;
+    }
+
+    fn update_rank(&mut self, node: &mut S::Link) {
+        let rank = |child: &Option<S::Link>| {
+            child
+                .as_ref()
+                .map_or(0, |child| self.nodes.node(child).rank + 1)
+        };
+        let current = self.nodes.node(node);
+        let (rank_left, rank_right) = (rank(&current.left), rank(&current.right));
+
+        let node = self.nodes.node_mut(node);
         node.rank = std::cmp::min(rank_left, rank_right);
         if rank_left < rank_right {
             std::mem::swap(&mut node.left, &mut node.right);
         }
     }
+}
 
-    fn size_helper(&self, node: &Option<Box<Node<T>>>) -> usize {
-        match node {
-            Some(node) => 1 + self.size_helper(&node.left) + self.size_helper(&node.right),
-            None => 0,
+impl<T, C, S: NodeStorage<Node<T, S>>> LeftistHeap<T, C, S> {
+    /// Free all nodes, collecting their elements in arbitrary order
+    fn take_elements(&mut self) -> Vec<T> {
+        let mut elements = Vec::new();
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            let node = self.nodes.free(node);
+            stack.extend(node.left);
+            stack.extend(node.right);
+            elements.push(node.element);
         }
+
+        self.size = 0;
+        elements
     }
 }
 
-impl<T: Ord> Heap<T> for LeftistHeap<T> {
+impl<T, C: Compare<T>> AddressableLeftistHeap<T, C> {
+    /// Restore ranks on the path from a node to the root after one of its subtrees changed
+    fn fix_ranks_upward(&mut self, node: usize) {
+        let mut current = Some(node);
+
+        while let Some(mut index) = current {
+            let old_rank = self.nodes.slab[index].rank;
+            self.update_rank(&mut index);
+
+            if self.nodes.slab[index].rank == old_rank {
+                break;
+            }
+            current = self.nodes.parents[index];
+        }
+    }
+
+    /// Detach a node from its parent, replacing it in the tree with `replacement`
+    fn replace_subtree(&mut self, node: usize, replacement: Option<usize>) {
+        let parent = self.nodes.parents[node].take();
+
+        if let Some(replacement) = replacement {
+            self.nodes.parents[replacement] = parent;
+        }
+
+        match parent {
+            None => self.root = replacement,
+            Some(parent) => {
+                let parent_node = &mut self.nodes.slab[parent];
+                if parent_node.left == Some(node) {
+                    parent_node.left = replacement;
+                } else {
+                    parent_node.right = replacement;
+                }
+                self.fix_ranks_upward(parent);
+            }
+        }
+    }
+
+    /// Remove a node from the tree, merging its two subtrees in its place
+    fn remove_node(&mut self, node: usize) -> T {
+        let Node {
+            element,
+            left,
+            right,
+            ..
+        } = self.nodes.free(node);
+        self.size -= 1;
+
+        for child in [left, right].into_iter().flatten() {
+            self.nodes.parents[child] = None;
+        }
+
+        let merged = self.merge_nodes(left, right);
+        self.replace_subtree(node, merged);
+
+        invariants::debug_check(self);
+        element
+    }
+}
+
+impl<T, C: Compare<T> + Default, S: NodeStorage<Node<T, S>>> Default for LeftistHeap<T, C, S> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> Heap<T> for LeftistHeap<T, C, S> {
     fn peek(&self) -> Option<&T> {
-        self.root.as_ref().map(|node| &node.element)
+        self.root
+            .as_ref()
+            .map(|node| &self.nodes.node(node).element)
     }
 
     fn pop(&mut self) -> Option<T> {
-        self.root.take().map(|mut node| {
-            let left = node.left.take();
-            let right = node.right.take();
-            self.root = Self::merge_nodes(left, right);
-            node.element
-        })
+        let root = self.root.take()?;
+        let Node {
+            element,
+            left,
+            right,
+            ..
+        } = self.nodes.free(root);
+        self.size -= 1;
+
+        for child in left.iter().chain(right.iter()) {
+            self.nodes.set_parent(child, None);
+        }
+        self.root = self.merge_nodes(left, right);
+
+        Some(element)
     }
 
     fn push(&mut self, element: T) {
-        let node = Box::new(Node::new(element));
-        self.root = Self::merge_nodes(self.root.take(), Some(node));
+        let node = self.nodes.alloc(Node::new(element));
+        let root = self.root.take();
+        self.root = self.merge_nodes(root, Some(node));
+        self.size += 1;
     }
 
     fn size(&self) -> usize {
-        self.size_helper(&self.root)
+        self.size
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        let mut stack: Vec<_> = self.root.iter().collect();
+
+        Box::new(std::iter::from_fn(move || {
+            let node = self.nodes.node(stack.pop()?);
+            stack.extend(node.left.iter().chain(node.right.iter()));
+            Some(&node.element)
+        }))
     }
 
+    /// Frees the nodes one by one, so that storages can release their slots
     fn clear(&mut self) {
-        self.root = None;
+        self.take_elements();
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.take_elements().into_iter())
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> IntoIterator for LeftistHeap<T, C, S> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the tree
+    fn into_iter(mut self) -> Self::IntoIter {
+        self.take_elements().into_iter()
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> MergeableHeap<T> for LeftistHeap<T, C, S> {
+    /// Merging the right spines takes O(log n), on top of moving the nodes of `other`
+    /// into this heap's storage. Handles of `other` are consumed along with it.
+    fn meld(&mut self, mut other: Self) {
+        let mut other_root = other.root.take();
+        self.nodes
+            .append(std::mem::take(&mut other.nodes), other_root.as_mut_slice());
+
+        let root = self.root.take();
+        self.root = self.merge_nodes(root, other_root);
+        self.size += std::mem::take(&mut other.size);
+        invariants::debug_check(self);
+    }
+}
+
+impl<T, C, S: NodeStorage<Node<T, S>>> Drop for LeftistHeap<T, C, S> {
+    fn drop(&mut self) {
+        self.take_elements();
+    }
+}
+
+impl<T, C: Compare<T> + Default, S: NodeStorage<Node<T, S>>> FromIterator<T>
+    for LeftistHeap<T, C, S>
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = Self::default();
+        heap.extend(iter);
+        heap
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> Extend<T> for LeftistHeap<T, C, S> {
+    /// Builds a heap of the new elements in O(k) and merges it in O(log(n + k))
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut queue = VecDeque::new();
+        for element in iter {
+            queue.push_back(self.nodes.alloc(Node::new(element)));
+        }
+        self.size += queue.len();
+
+        let merged = self.merge_queue(queue);
+        let root = self.root.take();
+        self.root = self.merge_nodes(root, merged);
+        invariants::debug_check(self);
+    }
+}
+
+impl<T, C: Compare<T>, S: NodeStorage<Node<T, S>>> HeapInvariants for LeftistHeap<T, C, S> {
+    /// Checks heap order, stored ranks and the leftist property of every node, along
+    /// with what the storage records about it, and the cached size
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        let rank = |child: &Option<S::Link>| {
+            child
+                .as_ref()
+                .map_or(0, |child| self.nodes.node(child).rank + 1)
+        };
+        let mut reachable = 0;
+        let mut stack: Vec<_> = self.root.iter().map(|root| (root, None)).collect();
+
+        while let Some((link, parent)) = stack.pop() {
+            let node = reachable;
+            reachable += 1;
+
+            self.nodes
+                .check_node(link, parent.map(|(parent_link, _)| parent_link), node)?;
+
+            let current = self.nodes.node(link);
+            if let Some((parent_link, parent)) = parent {
+                if self
+                    .compare
+                    .lt(&current.element, &self.nodes.node(parent_link).element)
+                {
+                    return Err(InvariantViolation::HeapOrder {
+                        parent,
+                        child: node,
+                    });
+                }
+            }
+
+            let (rank_left, rank_right) = (rank(&current.left), rank(&current.right));
+            if rank_left < rank_right {
+                return Err(InvariantViolation::Leftist { node });
+            }
+            if current.rank != rank_right {
+                return Err(InvariantViolation::Rank {
+                    node,
+                    stored: current.rank,
+                    expected: rank_right,
+                });
+            }
+
+            for child in current.left.iter().chain(current.right.iter()) {
+                stack.push((child, Some((link, node))));
+            }
+        }
+
+        if reachable != self.size {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected: self.size,
+            });
+        }
+
+        self.nodes.check_count(reachable)
+    }
+}
+
+impl<T, C: Compare<T>> AddressableHeap<T> for AddressableLeftistHeap<T, C> {
+    fn insert(&mut self, element: T) -> Handle {
+        let handle = self.nodes.insert(Node::new(element));
+        self.size += 1;
+        let root = self.root.take();
+        self.root = self.merge_nodes(root, Some(handle.index()));
+        invariants::debug_check(self);
+        handle
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes
+            .slab
+            .resolve(handle)
+            .map(|node| &self.nodes.slab[node].element)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) {
+        let node = self
+            .nodes
+            .slab
+            .resolve(handle)
+            .expect("Invalid heap handle");
+        assert!(
+            self.compare.le(&element, &self.nodes.slab[node].element),
+            "New key is greater than the current key"
+        );
+        self.nodes.slab[node].element = element;
+
+        // Cut the subtree out and merge it back in from the top
+        if self.nodes.parents[node].is_some() {
+            self.replace_subtree(node, None);
+            let root = self.root.take();
+            self.root = self.merge_nodes(root, Some(node));
+        }
+        invariants::debug_check(self);
+    }
+
+    fn delete(&mut self, handle: Handle) -> Option<T> {
+        self.nodes
+            .slab
+            .resolve(handle)
+            .map(|node| self.remove_node(node))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::ds::heap::Heap;
+    use crate::ds::heap::compare::{ByKey, MaxOrder};
+    use crate::ds::heap::invariants::InvariantViolation;
+    use crate::ds::heap::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
 
-    use super::LeftistHeap;
+    use super::{AddressableLeftistHeap, ArenaLeftistHeap, LeftistHeap};
 
     #[test]
     fn test_basic_heap() {
@@ -136,7 +641,7 @@ mod tests {
     #[test]
     fn test_heap() {
         // Create an instance of LeftistHeap that implements Heap
-        let mut heap: LeftistHeap<i32> = LeftistHeap { root: None };
+        let mut heap: LeftistHeap<i32> = LeftistHeap::new();
 
         // Test inserting elements
         for i in 1..=100 {
@@ -163,4 +668,181 @@ mod tests {
         assert_eq!(heap.peek(), None);
         assert_eq!(heap.pop(), None);
     }
+
+    #[test]
+    fn test_invariants() {
+        let mut heap = LeftistHeap::<i32>::from_vec((0..50).collect());
+        assert_eq!(heap.check_invariants(), Ok(()));
+
+        // Give the root a left spine shorter than its right one
+        let root = heap.root.as_mut().unwrap();
+        std::mem::swap(&mut root.left, &mut root.right);
+        assert_eq!(
+            heap.check_invariants(),
+            Err(InvariantViolation::Leftist { node: 0 })
+        );
+    }
+
+    #[test]
+    #[cfg_attr(feature = "check-invariants", ignore = "checks every push in O(n)")]
+    fn test_deep_trees() {
+        let mut heap = LeftistHeap::<i32>::new();
+        let mut other = LeftistHeap::<i32>::new();
+
+        // Descending pushes make every new root the parent of the previous tree, which
+        // ends up as its only child on the left
+        for i in (0..400_000).step_by(2).rev() {
+            heap.push(i);
+        }
+        for i in (1..400_000).step_by(2).rev() {
+            other.push(i);
+        }
+
+        heap.meld(other);
+
+        assert_eq!(heap.size(), 400_000);
+        for i in 0..1_000 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.size(), 399_000);
+        assert_eq!(heap.iter().count(), 399_000);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut heap = LeftistHeap::<i32>::from_vec((0..100).rev().collect());
+        heap.extend([-1, 150]);
+        heap.extend(100..150);
+
+        assert_eq!(heap.size(), 152);
+        for i in -1..=150 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+
+        let heap: LeftistHeap<i32, MaxOrder> = (0..100).collect();
+        assert_eq!(heap.peek(), Some(&99));
+    }
+
+    #[test]
+    fn test_handles() {
+        let mut heap = AddressableLeftistHeap::<i32>::new();
+
+        let handles: Vec<_> = (0..20).map(|i| heap.insert(100 + i)).collect();
+        assert_eq!(heap.pop(), Some(100));
+
+        heap.decrease_key(handles[15], 1);
+        heap.decrease_key(handles[10], 2);
+        assert_eq!(heap.get(handles[10]), Some(&2));
+
+        assert_eq!(heap.delete(handles[12]), Some(112));
+        assert_eq!(heap.delete(handles[12]), None);
+        assert_eq!(heap.get(handles[0]), None);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.size(), 16);
+        for i in (101..120).filter(|i| ![110, 112, 115].contains(i)) {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = AddressableLeftistHeap::<i32>::new();
+        let mut other = AddressableLeftistHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+
+        let handle = heap.insert(200);
+        heap.meld(other);
+        heap.decrease_key(handle, -1);
+
+        assert_eq!(heap.size(), 101);
+        assert_eq!(heap.pop(), Some(-1));
+        for i in 0..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_arena() {
+        let mut heap = ArenaLeftistHeap::<i32>::from_vec((0..100).rev().collect());
+        let mut other = ArenaLeftistHeap::<i32>::new();
+
+        other.extend((100..200).rev());
+        heap.meld(other);
+        for i in 0..50 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+
+        // Freed slots are reused by later pushes
+        heap.extend(0..50);
+        assert_eq!(heap.nodes.slab.slots(), 200);
+        assert_eq!(heap.check_invariants(), Ok(()));
+        for i in 0..200 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_max_heap() {
+        let mut heap = LeftistHeap::<i32, MaxOrder>::default();
+        let mut other = LeftistHeap::<i32, MaxOrder>::default();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+        heap.meld(other);
+
+        for i in (0..100).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_by_key() {
+        struct Task {
+            name: &'static str,
+            priority: u32,
+        }
+
+        let mut heap: AddressableLeftistHeap<_, _> =
+            LeftistHeap::with_comparator(ByKey(|task: &Task| task.priority));
+
+        heap.push(Task {
+            name: "write",
+            priority: 3,
+        });
+        heap.push(Task {
+            name: "read",
+            priority: 1,
+        });
+        let handle = heap.insert(Task {
+            name: "sleep",
+            priority: 9,
+        });
+        heap.decrease_key(
+            handle,
+            Task {
+                name: "eat",
+                priority: 2,
+            },
+        );
+
+        let order: Vec<_> = std::iter::from_fn(|| heap.pop().map(|task| task.name)).collect();
+        assert_eq!(order, ["read", "eat", "write"]);
+    }
 }
@@ -0,0 +1,283 @@
+use super::{DoubleEndedHeap, Heap};
+
+/// Heap stored in a vector whose levels alternate between min and max ordering.
+///
+/// Nodes on even levels are smaller than all their descendants and nodes on odd
+/// levels are larger, so the minimum sits at the root and the maximum is one of
+/// its children.
+pub struct MinMaxHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> Self {
+        MinMaxHeap { data: vec![] }
+    }
+
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ if self.data[1] >= self.data[2] => Some(1),
+            _ => Some(2),
+        }
+    }
+
+    fn is_min_level(index: usize) -> bool {
+        (index + 1).ilog2().is_multiple_of(2)
+    }
+
+    /// Whether the element at `a` should be closer to the root than the one at `b`
+    /// on a level with the given ordering
+    fn precedes(&self, a: usize, b: usize, min_level: bool) -> bool {
+        if min_level {
+            self.data[a] < self.data[b]
+        } else {
+            self.data[a] > self.data[b]
+        }
+    }
+
+    fn remove_at(&mut self, index: usize) -> T {
+        let removed_value = self.data.swap_remove(index);
+
+        if index < self.data.len() {
+            self.trickle_down(index);
+        }
+
+        removed_value
+    }
+
+    /// Move a newly inserted element up to the level where it belongs
+    fn bubble_up(&mut self, index: usize) {
+        if index == 0 {
+            return;
+        }
+
+        let parent = (index - 1) / 2;
+        let min_level = Self::is_min_level(index);
+
+        // An element out of order with its parent belongs on the other kind of level
+        if self.precedes(parent, index, min_level) {
+            self.data.swap(index, parent);
+            self.bubble_up_levels(parent, !min_level);
+        } else {
+            self.bubble_up_levels(index, min_level);
+        }
+    }
+
+    /// Move an element up through grandparents, staying on levels of one ordering
+    fn bubble_up_levels(&mut self, index: usize, min_level: bool) {
+        let mut index = index;
+
+        while index > 2 {
+            let grandparent = (index - 3) / 4;
+            if !self.precedes(index, grandparent, min_level) {
+                break;
+            }
+
+            self.data.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    /// Move an element down until both orderings hold below it
+    fn trickle_down(&mut self, index: usize) {
+        let min_level = Self::is_min_level(index);
+        let mut index = index;
+
+        loop {
+            // Find the best of children and grandchildren
+            let first_child = 2 * index + 1;
+            let first_grandchild = 4 * index + 3;
+            let descendants = (first_child..first_child + 2)
+                .chain(first_grandchild..first_grandchild + 4)
+                .filter(|&descendant| descendant < self.data.len());
+
+            let Some(best) = descendants.reduce(|best, d| {
+                if self.precedes(d, best, min_level) {
+                    d
+                } else {
+                    best
+                }
+            }) else {
+                break;
+            };
+
+            if !self.precedes(best, index, min_level) {
+                break;
+            }
+            self.data.swap(index, best);
+
+            if best < first_grandchild {
+                break;
+            }
+
+            // The element moved two levels down and may be out of order with its new parent
+            let parent = (best - 1) / 2;
+            if self.precedes(parent, best, min_level) {
+                self.data.swap(best, parent);
+            }
+            index = best;
+        }
+    }
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> DoubleEndedHeap<T> for MinMaxHeap<T> {
+    fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|index| &self.data[index])
+    }
+
+    fn pop_min(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        Some(self.remove_at(0))
+    }
+
+    fn pop_max(&mut self) -> Option<T> {
+        self.max_index().map(|index| self.remove_at(index))
+    }
+}
+
+impl<T: Ord> Heap<T> for MinMaxHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.peek_min()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+
+    fn push(&mut self, element: T) {
+        self.data.push(element);
+        self.bubble_up(self.data.len() - 1);
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear()
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.data.drain(..))
+    }
+}
+
+impl<T: Ord> IntoIterator for MinMaxHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::{DoubleEndedHeap, Heap};
+
+    use super::MinMaxHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = MinMaxHeap::<i32>::new();
+
+        heap.push(3);
+        heap.push(1);
+        heap.push(4);
+        heap.push(2);
+
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&4));
+
+        assert_eq!(heap.pop_max(), Some(4));
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(3));
+        assert_eq!(heap.pop_min(), Some(2));
+        assert_eq!(heap.pop_max(), None);
+        assert_eq!(heap.pop_min(), None);
+    }
+
+    #[test]
+    fn test_heap() {
+        let mut heap: MinMaxHeap<i32> = MinMaxHeap::new();
+
+        // Test inserting elements
+        for i in 1..=100 {
+            heap.push(i);
+        }
+
+        // Test inserting smaller elements after creating the heap
+        for i in -100..=0 {
+            heap.push(i);
+        }
+
+        // Test both extremes of the heap
+        assert_eq!(heap.peek(), Some(&-100));
+        assert_eq!(heap.peek_max(), Some(&100));
+
+        // Test popping all elements from the heap, alternating between both ends
+        for i in 0..=100 {
+            assert_eq!(heap.pop_min(), Some(i - 100));
+            if i < 100 {
+                assert_eq!(heap.pop_max(), Some(100 - i));
+            }
+        }
+
+        // Test heap is empty
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut heap = MinMaxHeap::<i32>::new();
+        let mut sorted = Vec::new();
+
+        for _ in 0..5_000 {
+            match rng.gen_range(0..4) {
+                0 | 1 => {
+                    let value = rng.gen_range(0..100);
+                    heap.push(value);
+                    let position = sorted.partition_point(|&x| x < value);
+                    sorted.insert(position, value);
+                }
+                2 => assert_eq!(
+                    heap.pop_min(),
+                    (!sorted.is_empty()).then(|| sorted.remove(0))
+                ),
+                _ => assert_eq!(heap.pop_max(), sorted.pop()),
+            }
+
+            assert_eq!(heap.peek_min(), sorted.first());
+            assert_eq!(heap.peek_max(), sorted.last());
+        }
+    }
+}
@@ -1,18 +1,171 @@
 pub mod binary_heap_vec;
 pub mod binomial_heap;
+pub mod compare;
+pub mod concurrent;
+pub mod d_ary_heap_vec;
+#[cfg(test)]
+mod differential;
+pub mod dual_heap;
+pub mod external;
+pub mod fibonacci_heap;
+pub mod handle;
+pub mod instrumented;
+pub mod interval_heap;
+pub mod invariants;
 pub mod leftist_heap;
+pub mod min_max_heap;
+pub mod pairing_heap;
+pub mod persistent;
+pub mod radix_heap;
+pub mod rank_pairing_heap;
+pub mod skew_heap;
+pub mod soft_heap;
+pub mod storage;
+pub mod weak_heap;
 
-pub trait Heap<T: Ord> {
+use std::marker::PhantomData;
+
+use handle::Handle;
+use invariants::InvariantViolation;
+
+pub trait Heap<T> {
     fn peek(&self) -> Option<&T>;
     fn pop(&mut self) -> Option<T>;
     fn push(&mut self, element: T);
     fn size(&self) -> usize;
 
+    /// Iterate over all elements in arbitrary order, leaving the heap untouched
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_>;
+
     fn clear(&mut self) {
-        while self.pop() != None {}
+        while self.pop().is_some() {}
+    }
+
+    /// Remove all elements, yielding them in arbitrary order.
+    ///
+    /// Elements not consumed by the time the iterator is dropped are removed as well.
+    /// The default pops them in heap order, heaps that can hand out their storage
+    /// directly do it in O(n).
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        Box::new(self.drain_sorted())
+    }
+
+    /// Remove all elements, yielding them in heap order.
+    ///
+    /// Elements not consumed by the time the iterator is dropped are removed as well.
+    fn drain_sorted(&mut self) -> DrainSorted<'_, T, Self> {
+        DrainSorted {
+            heap: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Consume the heap, returning its elements in heap order
+    fn into_sorted_vec(mut self) -> Vec<T>
+    where
+        Self: Sized,
+    {
+        self.drain_sorted().collect()
+    }
+}
+
+/// Iterator popping the elements of a heap, returned by [`Heap::drain_sorted`]
+pub struct DrainSorted<'a, T, H: Heap<T> + ?Sized> {
+    heap: &'a mut H,
+    marker: PhantomData<T>,
+}
+
+impl<T, H: Heap<T> + ?Sized> Iterator for DrainSorted<'_, T, H> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let size = self.heap.size();
+        (size, Some(size))
     }
 }
 
+impl<T, H: Heap<T> + ?Sized> ExactSizeIterator for DrainSorted<'_, T, H> {}
+
+impl<T, H: Heap<T> + ?Sized> Drop for DrainSorted<'_, T, H> {
+    fn drop(&mut self) {
+        self.heap.clear();
+    }
+}
+
+/// Heap giving access to both its smallest and its largest element
+pub trait DoubleEndedHeap<T: Ord>: Heap<T> {
+    fn peek_min(&self) -> Option<&T>;
+    fn peek_max(&self) -> Option<&T>;
+    fn pop_min(&mut self) -> Option<T>;
+    fn pop_max(&mut self) -> Option<T>;
+}
+
+/// Heap that can absorb all elements of another heap of the same kind
+pub trait MergeableHeap<T>: Heap<T> {
+    /// Move all elements of `other` into this heap
+    fn meld(&mut self, other: Self);
+}
+
+/// Heap whose elements can be updated or removed after insertion through handles
+pub trait AddressableHeap<T>: Heap<T> {
+    /// Push an element and return a handle referencing it
+    fn insert(&mut self, element: T) -> Handle;
+
+    /// Access the element behind a handle, if it is still in the heap
+    fn get(&self, handle: Handle) -> Option<&T>;
+
+    /// Replace the element behind a handle with one that comes no later in the heap's order.
+    ///
+    /// Panics if the handle is stale or was issued by another heap, or if the new element
+    /// comes after the current one.
+    fn decrease_key(&mut self, handle: Handle, element: T);
+
+    /// Remove the element behind a handle, returning `None` if the handle is stale or was
+    /// issued by another heap
+    fn delete(&mut self, handle: Handle) -> Option<T>;
+}
+
+/// Heap able to verify its own structure, for tests and debug builds
+pub trait HeapInvariants {
+    /// Walk the whole structure in O(n) and report the first broken property found
+    fn check_invariants(&self) -> Result<(), InvariantViolation>;
+}
+
+/// Priority queue of keyed values whose popped keys never decrease, as in Dijkstra's algorithm
+pub trait MonotoneHeap<K: Ord, V> {
+    /// Smallest key in the heap
+    fn peek_key(&self) -> Option<K>;
+
+    /// Remove an entry with the smallest key
+    fn pop(&mut self) -> Option<(K, V)>;
+
+    /// Add a value under a key.
+    ///
+    /// Panics if the key is smaller than the last popped one.
+    fn push(&mut self, key: K, value: V);
+
+    fn size(&self) -> usize;
+}
+
+/// Priority queue shared between threads, with every operation taking `&self`
+pub trait ConcurrentHeap<T>: Send + Sync {
+    fn push(&self, element: T);
+
+    /// Remove an element, which relaxed queues may pick from near the top instead of the
+    /// very first one. Returns `None` once the queue is seen empty.
+    fn pop(&self) -> Option<T>;
+
+    /// Number of elements, which may be outdated by the time it is read
+    fn size(&self) -> usize;
+}
+
 /// Insert a number of consecutive elements from 1 to n inclusive
 pub fn insert_n_elements<H: Heap<i32>>(heap: &mut H, n: i32) {
     for i in 1..n {
@@ -26,3 +179,284 @@ pub fn insert_n_vector_elements<H: Heap<i32>>(heap: &mut H, vec: &Vec<i32>) {
         heap.push(*i);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::panic::{self, AssertUnwindSafe};
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::binary_heap_vec::{AddressableBinaryHeap, BinaryHeapVec};
+    use super::binomial_heap::{AddressableBinomialHeap, ArenaBinomialHeap, BinomialHeap};
+    use super::d_ary_heap_vec::{AddressableDAryHeap, DAryHeapVec};
+    use super::dual_heap::DualHeap;
+    use super::fibonacci_heap::FibonacciHeap;
+    use super::handle::Handle;
+    use super::interval_heap::IntervalHeap;
+    use super::leftist_heap::{AddressableLeftistHeap, ArenaLeftistHeap, LeftistHeap};
+    use super::min_max_heap::MinMaxHeap;
+    use super::pairing_heap::PairingHeap;
+    use super::rank_pairing_heap::RankPairingHeap;
+    use super::skew_heap::SkewHeap;
+    use super::weak_heap::WeakHeap;
+    use super::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
+
+    #[derive(Clone, Copy, Debug)]
+    enum Operation {
+        Insert(i64),
+        Pop,
+        DecreaseKey(usize, i64),
+        Delete(usize),
+        Meld(i64, usize),
+    }
+
+    fn random_operations(seed: u64, count: usize) -> Vec<Operation> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        (0..count)
+            .map(|_| match rng.gen_range(0..10) {
+                0..=3 => Operation::Insert(rng.gen_range(0..1_000)),
+                4..=5 => Operation::Pop,
+                6..=7 => Operation::DecreaseKey(rng.gen(), rng.gen_range(1..500)),
+                8 => Operation::Delete(rng.gen()),
+                _ => Operation::Meld(rng.gen_range(0..1_000), rng.gen_range(0..20)),
+            })
+            .collect()
+    }
+
+    /// Replay operations on a heap and record everything it returns, passing the heap
+    /// to `inspect` after every operation.
+    ///
+    /// Keys are made unique by appending the operation counter, so that the set of
+    /// live handles does not depend on how an implementation breaks ties. Elements
+    /// that arrived through a meld have no handle.
+    fn replay<H>(operations: &[Operation], inspect: impl Fn(&H)) -> Vec<Option<i64>>
+    where
+        H: AddressableHeap<i64> + MergeableHeap<i64> + Default,
+    {
+        let mut heap = H::default();
+        let mut live: Vec<(Option<Handle>, i64)> = Vec::new();
+        let mut results = Vec::new();
+
+        for (step, operation) in operations.iter().enumerate() {
+            let unique = |key: i64| key * 100_000 + step as i64;
+
+            match *operation {
+                Operation::Insert(key) => {
+                    let key = unique(key);
+                    live.push((Some(heap.insert(key)), key));
+                }
+                Operation::Pop => {
+                    let popped = heap.pop();
+                    assert_eq!(popped, live.iter().map(|&(_, k)| k).min());
+                    if let Some(key) = popped {
+                        let position = live.iter().position(|&(_, k)| k == key).unwrap();
+                        if let (Some(handle), _) = live.swap_remove(position) {
+                            assert_eq!(heap.get(handle), None);
+                        }
+                    }
+                    results.push(popped);
+                }
+                Operation::DecreaseKey(choice, delta) if !live.is_empty() => {
+                    let index = choice % live.len();
+                    let (Some(handle), key) = live[index] else {
+                        continue;
+                    };
+                    let new_key = unique(key.div_euclid(100_000) - delta);
+
+                    heap.decrease_key(handle, new_key);
+                    live[index].1 = new_key;
+                    results.push(heap.get(handle).copied());
+                }
+                Operation::Delete(choice) if !live.is_empty() => {
+                    let index = choice % live.len();
+                    let (Some(handle), key) = live[index] else {
+                        continue;
+                    };
+                    live.swap_remove(index);
+                    assert_eq!(heap.delete(handle), Some(key));
+                    results.push(Some(key));
+                    results.push(heap.delete(handle));
+                }
+                Operation::Meld(base, count) => {
+                    let mut other = H::default();
+                    for key in (base..).take(count).map(unique) {
+                        other.push(key);
+                        live.push((None, key));
+                    }
+                    heap.meld(other);
+                }
+                _ => {}
+            }
+
+            results.push(heap.peek().copied());
+            assert_eq!(heap.size(), live.len());
+            inspect(&heap);
+        }
+
+        while let Some(key) = heap.pop() {
+            results.push(Some(key));
+        }
+
+        results
+    }
+
+    #[test]
+    fn test_heaps_agree() {
+        // Invariant checks after every operation make each replay quadratic
+        let (seeds, count) = if cfg!(feature = "check-invariants") {
+            (5, 500)
+        } else {
+            (20, 2_000)
+        };
+
+        for seed in 0..seeds {
+            let operations = random_operations(seed, count);
+            let expected = replay::<AddressableBinaryHeap<i64>>(&operations, |_| {});
+
+            assert_eq!(
+                replay::<AddressableDAryHeap<i64, 4>>(&operations, |_| {}),
+                expected
+            );
+            assert_eq!(
+                replay::<AddressableLeftistHeap<i64>>(&operations, |_| {}),
+                expected
+            );
+            assert_eq!(
+                replay::<AddressableBinomialHeap<i64>>(&operations, |_| {}),
+                expected
+            );
+            assert_eq!(replay::<FibonacciHeap<i64>>(&operations, |_| {}), expected);
+            assert_eq!(
+                replay::<RankPairingHeap<i64>>(&operations, |_| {}),
+                expected
+            );
+        }
+    }
+
+    fn assert_invariants<H: HeapInvariants>(heap: &H) {
+        if let Err(violation) = heap.check_invariants() {
+            panic!("Heap invariant violated: {violation}");
+        }
+    }
+
+    #[test]
+    fn test_invariants_hold() {
+        for seed in 0..5 {
+            let operations = random_operations(seed, 500);
+
+            replay::<AddressableBinaryHeap<i64>>(&operations, assert_invariants);
+            replay::<AddressableDAryHeap<i64, 4>>(&operations, assert_invariants);
+            replay::<AddressableLeftistHeap<i64>>(&operations, assert_invariants);
+            replay::<AddressableBinomialHeap<i64>>(&operations, assert_invariants);
+            replay::<RankPairingHeap<i64>>(&operations, assert_invariants);
+        }
+    }
+
+    /// Meld a heap whose handles number their slots like this heap's, then check that
+    /// those handles reach nothing, while the heap's own handles still work
+    fn check_melded_handles<H>()
+    where
+        H: AddressableHeap<i32> + MergeableHeap<i32> + Default,
+    {
+        let mut heap = H::default();
+        let mut other = H::default();
+        let own = heap.insert(5);
+        let melded = other.insert(10);
+        heap.meld(other);
+
+        assert_eq!(heap.get(melded), None);
+        assert_eq!(heap.delete(melded), None);
+        let decreased = panic::catch_unwind(AssertUnwindSafe(|| heap.decrease_key(melded, 1)));
+        assert!(decreased.is_err());
+
+        let foreign = H::default().insert(20);
+        assert_eq!(heap.get(foreign), None);
+
+        heap.decrease_key(own, 1);
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(10));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_melded_handles() {
+        check_melded_handles::<AddressableBinaryHeap<i32>>();
+        check_melded_handles::<AddressableDAryHeap<i32, 4>>();
+        check_melded_handles::<AddressableLeftistHeap<i32>>();
+        check_melded_handles::<AddressableBinomialHeap<i32>>();
+        check_melded_handles::<FibonacciHeap<i32>>();
+        check_melded_handles::<RankPairingHeap<i32>>();
+    }
+
+    /// Heap holding 10..200 after popping the ten smallest of 0..200
+    fn build_heap<H: Heap<i32> + Default>() -> H {
+        let mut heap = H::default();
+        for i in 0..200 {
+            heap.push(i * 37 % 200);
+        }
+        for _ in 0..10 {
+            heap.pop();
+        }
+        heap
+    }
+
+    fn check_iterators<H: Heap<i32> + Default + IntoIterator<Item = i32>>() {
+        let expected: Vec<i32> = (10..200).collect();
+
+        let heap = build_heap::<H>();
+        let mut elements: Vec<i32> = heap.iter().copied().collect();
+        elements.sort();
+        assert_eq!(elements, expected);
+        assert_eq!(heap.size(), 190);
+
+        let mut elements: Vec<i32> = heap.into_iter().collect();
+        elements.sort();
+        assert_eq!(elements, expected);
+
+        let mut heap = build_heap::<H>();
+        let mut elements: Vec<i32> = heap.drain().collect();
+        elements.sort();
+        assert_eq!(elements, expected);
+        assert_eq!(heap.size(), 0);
+
+        // Dropping a partially consumed drain still empties the heap
+        let mut heap = build_heap::<H>();
+        assert_eq!(heap.drain().take(5).count(), 5);
+        assert_eq!(heap.size(), 0);
+        assert_eq!(heap.iter().count(), 0);
+        heap.push(7);
+        assert_eq!(heap.pop(), Some(7));
+
+        let mut heap = build_heap::<H>();
+        assert_eq!(
+            heap.drain_sorted().take(3).collect::<Vec<_>>(),
+            [10, 11, 12]
+        );
+        assert_eq!(heap.size(), 0);
+
+        assert_eq!(build_heap::<H>().into_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn test_iterators() {
+        check_iterators::<BinaryHeapVec<i32>>();
+        check_iterators::<AddressableBinaryHeap<i32>>();
+        check_iterators::<DAryHeapVec<i32, 4>>();
+        check_iterators::<LeftistHeap<i32>>();
+        check_iterators::<AddressableLeftistHeap<i32>>();
+        check_iterators::<BinomialHeap<i32>>();
+        check_iterators::<AddressableBinomialHeap<i32>>();
+        check_iterators::<FibonacciHeap<i32>>();
+        check_iterators::<PairingHeap<i32>>();
+        check_iterators::<SkewHeap<i32>>();
+        check_iterators::<MinMaxHeap<i32>>();
+        check_iterators::<IntervalHeap<i32>>();
+        check_iterators::<DualHeap<i32>>();
+        check_iterators::<ArenaLeftistHeap<i32>>();
+        check_iterators::<ArenaBinomialHeap<i32>>();
+        check_iterators::<WeakHeap<i32>>();
+        check_iterators::<RankPairingHeap<i32>>();
+    }
+}
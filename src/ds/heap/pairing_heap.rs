@@ -0,0 +1,309 @@
+use super::handle::Slab;
+use super::{Heap, MergeableHeap};
+
+/// Order in which the children of a popped root are linked back into a single tree
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PopStrategy {
+    /// Link children in pairs left to right, then fold the pairs right to left
+    #[default]
+    TwoPass,
+    /// Keep linking pairs in rounds until a single tree remains
+    Multipass,
+    /// Link children in pairs left to right, then fold the pairs left to right as well
+    FrontToBack,
+}
+
+/// Nodes use the left-child, right-sibling representation
+struct Node<T> {
+    element: T,
+    child: Option<usize>,
+    sibling: Option<usize>,
+}
+
+impl<T> Node<T> {
+    fn new(element: T) -> Self {
+        Node {
+            element,
+            child: None,
+            sibling: None,
+        }
+    }
+}
+
+pub struct PairingHeap<T> {
+    nodes: Slab<Node<T>>,
+    root: Option<usize>,
+    strategy: PopStrategy,
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        Self::with_strategy(PopStrategy::default())
+    }
+
+    pub fn with_strategy(strategy: PopStrategy) -> Self {
+        PairingHeap {
+            nodes: Slab::new(),
+            root: None,
+            strategy,
+        }
+    }
+
+    pub fn strategy(&self) -> PopStrategy {
+        self.strategy
+    }
+
+    /// Link two trees, the one with the larger root becoming the first child of the other
+    fn link(&mut self, first: usize, second: usize) -> usize {
+        let (parent, child) = if self.nodes[first].element <= self.nodes[second].element {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        self.nodes[child].sibling = self.nodes[parent].child;
+        self.nodes[parent].child = Some(child);
+        parent
+    }
+
+    fn merge_roots(&mut self, first: Option<usize>, second: Option<usize>) -> Option<usize> {
+        match (first, second) {
+            (None, second) => second,
+            (first, None) => first,
+            (Some(first), Some(second)) => Some(self.link(first, second)),
+        }
+    }
+
+    /// Detach the children of a node into a list of independent trees
+    fn take_children(&mut self, node: usize) -> Vec<usize> {
+        let mut children = Vec::new();
+        let mut current = self.nodes[node].child.take();
+
+        while let Some(child) = current {
+            current = self.nodes[child].sibling.take();
+            children.push(child);
+        }
+
+        children
+    }
+
+    /// Link children in pairs left to right
+    fn pairing_pass(&mut self, trees: Vec<usize>) -> Vec<usize> {
+        let mut pairs = Vec::with_capacity(trees.len().div_ceil(2));
+
+        for pair in trees.chunks(2) {
+            match *pair {
+                [first, second] => pairs.push(self.link(first, second)),
+                [single] => pairs.push(single),
+                _ => unreachable!(),
+            }
+        }
+
+        pairs
+    }
+
+    fn combine_two_pass(&mut self, trees: Vec<usize>) -> Option<usize> {
+        self.pairing_pass(trees)
+            .into_iter()
+            .rev()
+            .reduce(|accumulated, tree| self.link(tree, accumulated))
+    }
+
+    fn combine_multipass(&mut self, trees: Vec<usize>) -> Option<usize> {
+        let mut queue = std::collections::VecDeque::from(trees);
+
+        while queue.len() > 1 {
+            let first = queue.pop_front().unwrap();
+            let second = queue.pop_front().unwrap();
+            let linked = self.link(first, second);
+            queue.push_back(linked);
+        }
+
+        queue.pop_front()
+    }
+
+    fn combine_front_to_back(&mut self, trees: Vec<usize>) -> Option<usize> {
+        self.pairing_pass(trees)
+            .into_iter()
+            .reduce(|accumulated, tree| self.link(accumulated, tree))
+    }
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Heap<T> for PairingHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.root.map(|root| &self.nodes[root].element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let children = self.take_children(root);
+
+        self.root = match self.strategy {
+            PopStrategy::TwoPass => self.combine_two_pass(children),
+            PopStrategy::Multipass => self.combine_multipass(children),
+            PopStrategy::FrontToBack => self.combine_front_to_back(children),
+        };
+
+        Some(self.nodes.remove(root).element)
+    }
+
+    fn push(&mut self, element: T) {
+        let node = self.nodes.insert(Node::new(element)).index();
+        self.root = self.merge_roots(self.root, Some(node));
+    }
+
+    fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.nodes.values().map(|node| &node.element))
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        self.root = None;
+        Box::new(self.nodes.drain().into_iter().map(|node| node.element))
+    }
+}
+
+impl<T: Ord> IntoIterator for PairingHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the slab
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes
+            .into_values()
+            .map(|node| node.element)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for PairingHeap<T> {
+    /// Linking the roots is O(1), on top of moving the nodes of `other` into this heap's slab
+    fn meld(&mut self, other: Self) {
+        let offset = self.nodes.slots();
+        self.nodes.append(other.nodes, |node| {
+            node.child = node.child.map(|index| index + offset);
+            node.sibling = node.sibling.map(|index| index + offset);
+        });
+
+        let other_root = other.root.map(|index| index + offset);
+        self.root = self.merge_roots(self.root, other_root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ds::heap::{Heap, MergeableHeap};
+
+    use super::{PairingHeap, PopStrategy};
+
+    const STRATEGIES: [PopStrategy; 3] = [
+        PopStrategy::TwoPass,
+        PopStrategy::Multipass,
+        PopStrategy::FrontToBack,
+    ];
+
+    #[test]
+    fn test_basic_heap() {
+        for strategy in STRATEGIES {
+            let mut heap = PairingHeap::<i32>::with_strategy(strategy);
+
+            heap.push(1);
+            heap.push(2);
+            heap.push(3);
+            heap.push(4);
+
+            assert_eq!(heap.pop(), Some(1));
+            assert_eq!(heap.pop(), Some(2));
+            assert_eq!(heap.pop(), Some(3));
+            assert_eq!(heap.pop(), Some(4));
+            assert_eq!(heap.pop(), None);
+
+            heap.push(4);
+            heap.push(1);
+            heap.push(3);
+            heap.push(2);
+
+            assert_eq!(heap.pop(), Some(1));
+            assert_eq!(heap.pop(), Some(2));
+            assert_eq!(heap.pop(), Some(3));
+            assert_eq!(heap.pop(), Some(4));
+            assert_eq!(heap.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_heap() {
+        for strategy in STRATEGIES {
+            let mut heap = PairingHeap::<i32>::with_strategy(strategy);
+
+            // Test inserting elements
+            for i in 1..=100 {
+                heap.push(i);
+            }
+
+            // Test the smallest element in the heap
+            assert_eq!(heap.peek(), Some(&1));
+
+            // Test inserting smaller elements after creating the heap
+            for i in -100..=0 {
+                heap.push(i);
+            }
+
+            // Test the smallest element in the heap
+            assert_eq!(heap.peek(), Some(&-100));
+            assert_eq!(heap.size(), 201);
+
+            // Test popping all elements from the heap
+            for i in -100..=100 {
+                assert_eq!(heap.pop(), Some(i));
+            }
+
+            // Test heap is empty
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_meld() {
+        for strategy in STRATEGIES {
+            let mut heap = PairingHeap::<i32>::with_strategy(strategy);
+            let mut other = PairingHeap::<i32>::with_strategy(strategy);
+
+            for i in (0..100).step_by(2) {
+                heap.push(i);
+            }
+            for i in (1..100).step_by(2) {
+                other.push(i);
+            }
+            other.pop();
+
+            heap.meld(other);
+
+            assert_eq!(heap.size(), 99);
+            assert_eq!(heap.pop(), Some(0));
+            for i in 2..100 {
+                assert_eq!(heap.pop(), Some(i));
+            }
+            assert_eq!(heap.pop(), None);
+        }
+    }
+}
@@ -0,0 +1,515 @@
+use std::sync::Arc;
+
+struct LeftistNode<T> {
+    element: T,
+    left: Option<Arc<LeftistNode<T>>>,
+    right: Option<Arc<LeftistNode<T>>>,
+    rank: usize,
+}
+
+/// Persistent leftist heap, whose operations return new versions and leave the old ones
+/// valid.
+///
+/// Versions share every node an operation does not touch. Merging copies the right spines
+/// it walks along with their elements, so large elements are best wrapped in `Rc` or `Arc`.
+/// Nodes are reference counted with `Arc`, so versions can be handed to other threads.
+pub struct PersistentLeftistHeap<T> {
+    root: Option<Arc<LeftistNode<T>>>,
+    size: usize,
+}
+
+impl<T: Ord + Clone> PersistentLeftistHeap<T> {
+    pub fn new() -> Self {
+        PersistentLeftistHeap {
+            root: None,
+            size: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|node| &node.element)
+    }
+
+    /// Version holding one more element, in O(log n)
+    pub fn push(&self, element: T) -> Self {
+        let node = Arc::new(LeftistNode {
+            element,
+            left: None,
+            right: None,
+            rank: 0,
+        });
+
+        PersistentLeftistHeap {
+            root: Self::merge_nodes(&self.root, &Some(node)),
+            size: self.size + 1,
+        }
+    }
+
+    /// The first element together with the version holding the rest, in O(log n)
+    pub fn pop(&self) -> Option<(T, Self)> {
+        let root = self.root.as_ref()?;
+        let rest = PersistentLeftistHeap {
+            root: Self::merge_nodes(&root.left, &root.right),
+            size: self.size - 1,
+        };
+
+        Some((root.element.clone(), rest))
+    }
+
+    /// Version holding the elements of both heaps, in O(log n + log m)
+    pub fn meld(&self, other: &Self) -> Self {
+        PersistentLeftistHeap {
+            root: Self::merge_nodes(&self.root, &other.root),
+            size: self.size + other.size,
+        }
+    }
+
+    /// Elements in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut stack: Vec<&LeftistNode<T>> = self.root.as_deref().into_iter().collect();
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.left.as_deref());
+            stack.extend(node.right.as_deref());
+            Some(&node.element)
+        })
+    }
+
+    /// Merge two trees along their right spines, copying the nodes on the merged path.
+    ///
+    /// Right spines are O(log n) long, which bounds the recursion depth.
+    fn merge_nodes(
+        left: &Option<Arc<LeftistNode<T>>>,
+        right: &Option<Arc<LeftistNode<T>>>,
+    ) -> Option<Arc<LeftistNode<T>>> {
+        match (left, right) {
+            (None, right) => right.clone(),
+            (left, None) => left.clone(),
+            (Some(left_node), Some(right_node)) => {
+                let (top, other) = if left_node.element <= right_node.element {
+                    (left_node, right_node)
+                } else {
+                    (right_node, left_node)
+                };
+
+                let merged = Self::merge_nodes(&top.right, &Some(other.clone()));
+                Some(Self::make_node(
+                    top.element.clone(),
+                    top.left.clone(),
+                    merged,
+                ))
+            }
+        }
+    }
+
+    /// Create a node, putting the child with the larger rank on the left
+    fn make_node(
+        element: T,
+        first: Option<Arc<LeftistNode<T>>>,
+        second: Option<Arc<LeftistNode<T>>>,
+    ) -> Arc<LeftistNode<T>> {
+        let rank = |node: &Option<Arc<LeftistNode<T>>>| node.as_ref().map_or(0, |n| n.rank + 1);
+        let (left, right) = if rank(&first) < rank(&second) {
+            (second, first)
+        } else {
+            (first, second)
+        };
+
+        Arc::new(LeftistNode {
+            element,
+            rank: rank(&right),
+            left,
+            right,
+        })
+    }
+}
+
+impl<T: Ord + Clone> Default for PersistentLeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> Clone for PersistentLeftistHeap<T> {
+    /// Versions are shared, so cloning one takes O(1)
+    fn clone(&self) -> Self {
+        PersistentLeftistHeap {
+            root: self.root.clone(),
+            size: self.size,
+        }
+    }
+}
+
+impl<T> Drop for PersistentLeftistHeap<T> {
+    /// Left paths grow as long as the heap, so nodes no other version holds are freed from
+    /// an explicit stack instead of through the recursive drop of `Arc`
+    fn drop(&mut self) {
+        let mut stack: Vec<_> = self.root.take().into_iter().collect();
+
+        while let Some(node) = stack.pop() {
+            if let Some(mut node) = Arc::into_inner(node) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+struct BinomialNode<T> {
+    element: T,
+    /// Order of the tree rooted at this node
+    order: usize,
+    /// Child of the highest order, the other children being linked through its siblings
+    child: Option<Arc<BinomialNode<T>>>,
+    /// Next child of the same parent, of one order less; `None` for roots
+    sibling: Option<Arc<BinomialNode<T>>>,
+}
+
+/// Persistent binomial heap, sharing nodes between versions like
+/// [`PersistentLeftistHeap`].
+///
+/// Operations copy the roots they link, the children a pop promotes to roots and the list
+/// of roots.
+pub struct PersistentBinomialHeap<T> {
+    /// Roots by strictly increasing order
+    trees: Vec<Arc<BinomialNode<T>>>,
+    size: usize,
+}
+
+impl<T: Ord + Clone> PersistentBinomialHeap<T> {
+    pub fn new() -> Self {
+        PersistentBinomialHeap {
+            trees: Vec::new(),
+            size: 0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.first_tree()
+            .map(|position| &self.trees[position].element)
+    }
+
+    /// Version holding one more element, in O(log n)
+    pub fn push(&self, element: T) -> Self {
+        let node = Arc::new(BinomialNode {
+            element,
+            order: 0,
+            child: None,
+            sibling: None,
+        });
+
+        PersistentBinomialHeap {
+            trees: Self::merge_trees(&self.trees, &[node]),
+            size: self.size + 1,
+        }
+    }
+
+    /// The first element together with the version holding the rest, in O(log n)
+    pub fn pop(&self) -> Option<(T, Self)> {
+        let position = self.first_tree()?;
+        let root = &self.trees[position];
+
+        // Children are linked by decreasing order, roots are listed by increasing order.
+        // Each child is copied to become a root without a sibling.
+        let mut children = Vec::with_capacity(root.order);
+        let mut child = root.child.as_deref();
+        while let Some(node) = child {
+            child = node.sibling.as_deref();
+            children.push(Arc::new(BinomialNode {
+                element: node.element.clone(),
+                order: node.order,
+                child: node.child.clone(),
+                sibling: None,
+            }));
+        }
+        children.reverse();
+
+        let mut trees = self.trees.clone();
+        trees.remove(position);
+
+        let rest = PersistentBinomialHeap {
+            trees: Self::merge_trees(&trees, &children),
+            size: self.size - 1,
+        };
+
+        Some((root.element.clone(), rest))
+    }
+
+    /// Version holding the elements of both heaps, in O(log n + log m)
+    pub fn meld(&self, other: &Self) -> Self {
+        PersistentBinomialHeap {
+            trees: Self::merge_trees(&self.trees, &other.trees),
+            size: self.size + other.size,
+        }
+    }
+
+    /// Elements in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        let mut stack: Vec<&BinomialNode<T>> = self.trees.iter().map(|tree| &**tree).collect();
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.child.as_deref());
+            stack.extend(node.sibling.as_deref());
+            Some(&node.element)
+        })
+    }
+
+    /// Position in `trees` of the tree whose root comes first
+    fn first_tree(&self) -> Option<usize> {
+        self.trees
+            .iter()
+            .enumerate()
+            .min_by(|(_, x), (_, y)| x.element.cmp(&y.element))
+            .map(|(position, _)| position)
+    }
+
+    /// Link two trees of the same order, copying both roots since the one with the larger
+    /// element gains a sibling and the other one a child
+    fn link(first: &Arc<BinomialNode<T>>, second: &Arc<BinomialNode<T>>) -> Arc<BinomialNode<T>> {
+        let (parent, child) = if first.element <= second.element {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let child = Arc::new(BinomialNode {
+            element: child.element.clone(),
+            order: child.order,
+            child: child.child.clone(),
+            sibling: parent.child.clone(),
+        });
+
+        Arc::new(BinomialNode {
+            element: parent.element.clone(),
+            order: parent.order + 1,
+            child: Some(child),
+            sibling: None,
+        })
+    }
+
+    /// Add two lists of trees like binary numbers, both listed by strictly increasing
+    /// order, so that the result holds at most one tree of each order
+    fn merge_trees(
+        first: &[Arc<BinomialNode<T>>],
+        second: &[Arc<BinomialNode<T>>],
+    ) -> Vec<Arc<BinomialNode<T>>> {
+        let mut first = first.iter().cloned().peekable();
+        let mut second = second.iter().cloned().peekable();
+        let mut carry: Option<Arc<BinomialNode<T>>> = None;
+        let mut trees = Vec::new();
+
+        while let Some(order) = [first.peek(), second.peek(), carry.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(|tree| tree.order)
+            .min()
+        {
+            // Collect the up to three trees of the lowest order
+            let same: Vec<_> = [
+                carry.take_if(|tree| tree.order == order),
+                first.next_if(|tree| tree.order == order),
+                second.next_if(|tree| tree.order == order),
+            ]
+            .into_iter()
+            .flatten()
+            .collect();
+
+            match &same[..] {
+                [tree] => trees.push(tree.clone()),
+                [tree1, tree2] => carry = Some(Self::link(tree1, tree2)),
+                [tree, tree1, tree2] => {
+                    trees.push(tree.clone());
+                    carry = Some(Self::link(tree1, tree2));
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        trees
+    }
+}
+
+impl<T: Ord + Clone> Default for PersistentBinomialHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> Clone for PersistentBinomialHeap<T> {
+    /// Cloning a version copies its O(log n) roots
+    fn clone(&self) -> Self {
+        PersistentBinomialHeap {
+            trees: self.trees.clone(),
+            size: self.size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::{PersistentBinomialHeap, PersistentLeftistHeap};
+
+    /// Operations shared by both persistent heaps, to test them alike
+    trait Persistent: Clone + Default {
+        fn push(&self, element: i32) -> Self;
+        fn pop(&self) -> Option<(i32, Self)>;
+        fn meld(&self, other: &Self) -> Self;
+        fn size(&self) -> usize;
+        fn elements(&self) -> Vec<i32>;
+    }
+
+    macro_rules! impl_persistent {
+        ($heap:ident) => {
+            impl Persistent for $heap<i32> {
+                fn push(&self, element: i32) -> Self {
+                    $heap::push(self, element)
+                }
+
+                fn pop(&self) -> Option<(i32, Self)> {
+                    $heap::pop(self)
+                }
+
+                fn meld(&self, other: &Self) -> Self {
+                    $heap::meld(self, other)
+                }
+
+                fn size(&self) -> usize {
+                    $heap::size(self)
+                }
+
+                fn elements(&self) -> Vec<i32> {
+                    self.iter().copied().collect()
+                }
+            }
+        };
+    }
+
+    impl_persistent!(PersistentLeftistHeap);
+    impl_persistent!(PersistentBinomialHeap);
+
+    fn drain<H: Persistent>(heap: &H) -> Vec<i32> {
+        let mut elements = Vec::new();
+        let mut current = heap.clone();
+
+        while let Some((element, rest)) = current.pop() {
+            elements.push(element);
+            current = rest;
+        }
+
+        elements
+    }
+
+    fn check_versions<H: Persistent>() {
+        let empty = H::default();
+        let small = [5, 1, 4]
+            .iter()
+            .fold(empty.clone(), |heap, &i| heap.push(i));
+        let large = (10..20).fold(small.clone(), |heap, i| heap.push(i));
+
+        let (first, rest) = small.pop().unwrap();
+        assert_eq!(first, 1);
+        assert_eq!(drain(&rest), vec![4, 5]);
+
+        // Pushing onto and popping from a version leaves it untouched
+        assert_eq!(empty.size(), 0);
+        assert_eq!(drain(&small), vec![1, 4, 5]);
+        assert_eq!(large.size(), 13);
+
+        let mut expected: Vec<_> = [1, 4, 5].into_iter().chain(10..20).collect();
+        assert_eq!(drain(&large), expected);
+
+        let melded = large.meld(&rest.push(0));
+        expected.extend([0, 4, 5]);
+        expected.sort();
+        assert_eq!(melded.size(), 16);
+        assert_eq!(drain(&melded), expected);
+        assert_eq!(drain(&large).len(), 13);
+    }
+
+    /// Grow a tree of versions, checking each branch against a sorted vector
+    fn check_branches<H: Persistent>(seed: u64) {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut versions = vec![(H::default(), Vec::new())];
+
+        for _ in 0..500 {
+            let (heap, expected) = versions[rng.gen_range(0..versions.len())].clone();
+
+            let version = match rng.gen_range(0..3) {
+                0 | 1 => {
+                    let key = rng.gen_range(0..50);
+                    let mut expected = expected;
+                    expected.push(key);
+                    expected.sort();
+                    (heap.push(key), expected)
+                }
+                _ => match heap.pop() {
+                    Some((element, rest)) => {
+                        assert_eq!(element, expected[0]);
+                        (rest, expected[1..].to_vec())
+                    }
+                    None => {
+                        assert!(expected.is_empty());
+                        (heap, expected)
+                    }
+                },
+            };
+
+            versions.push(version);
+        }
+
+        for (heap, expected) in &versions {
+            assert_eq!(heap.size(), expected.len());
+            assert_eq!(&drain(heap), expected);
+
+            let mut elements = heap.elements();
+            elements.sort();
+            assert_eq!(&elements, expected);
+        }
+    }
+
+    #[test]
+    fn test_versions() {
+        check_versions::<PersistentLeftistHeap<i32>>();
+        check_versions::<PersistentBinomialHeap<i32>>();
+    }
+
+    #[test]
+    fn test_branches() {
+        for seed in 0..5 {
+            check_branches::<PersistentLeftistHeap<i32>>(seed);
+            check_branches::<PersistentBinomialHeap<i32>>(seed);
+        }
+    }
+
+    #[test]
+    fn test_deep_leftist_drop() {
+        // Every new minimum takes the previous tree as its left child
+        let heap = (0..400_000)
+            .rev()
+            .fold(PersistentLeftistHeap::new(), |heap, i| heap.push(i));
+
+        assert_eq!(heap.size(), 400_000);
+        assert_eq!(heap.peek(), Some(&0));
+    }
+}
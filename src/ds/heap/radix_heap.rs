@@ -0,0 +1,205 @@
+use super::instrumented::Counted;
+use super::MonotoneHeap;
+
+/// Unsigned integer that can serve as a radix heap key
+pub trait RadixKey: Copy + Ord + Default {
+    const BITS: u32;
+
+    /// Number of significant bits in `self ^ other`
+    fn differing_bits(self, other: Self) -> u32;
+}
+
+macro_rules! impl_radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn differing_bits(self, other: Self) -> u32 {
+                    <$t>::BITS - (self ^ other).leading_zeros()
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key!(u8, u16, u32, u64, u128, usize);
+
+impl<K: RadixKey> RadixKey for Counted<K> {
+    const BITS: u32 = K::BITS;
+
+    fn differing_bits(self, other: Self) -> u32 {
+        self.0.differing_bits(other.0)
+    }
+}
+
+/// Monotone priority queue for unsigned integer keys.
+///
+/// Entries are kept in buckets by the highest bit in which their key differs from the
+/// last popped key. When the first bucket runs dry, the next non-empty bucket is split
+/// around its smallest key, and every entry moves to a strictly lower bucket, so each
+/// entry is moved at most `K::BITS` times over its lifetime.
+pub struct RadixHeap<K, V> {
+    buckets: Vec<Vec<(K, V)>>,
+    last: K,
+    size: usize,
+}
+
+impl<K: RadixKey, V> RadixHeap<K, V> {
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+            last: K::default(),
+            size: 0,
+        }
+    }
+
+    /// Last popped key, below which no key can be pushed anymore
+    pub fn last(&self) -> K {
+        self.last
+    }
+
+    fn bucket(&self, key: K) -> usize {
+        key.differing_bits(self.last) as usize
+    }
+
+    /// Refill the first bucket from the next non-empty one
+    fn redistribute(&mut self) {
+        let Some(index) = self.buckets.iter().position(|bucket| !bucket.is_empty()) else {
+            return;
+        };
+        if index == 0 {
+            return;
+        }
+
+        let entries = std::mem::take(&mut self.buckets[index]);
+        self.last = entries.iter().map(|(key, _)| *key).min().unwrap();
+
+        for (key, value) in entries {
+            let bucket = self.bucket(key);
+            self.buckets[bucket].push((key, value));
+        }
+    }
+}
+
+impl<K: RadixKey, V> Default for RadixHeap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: RadixKey, V> MonotoneHeap<K, V> for RadixHeap<K, V> {
+    fn peek_key(&self) -> Option<K> {
+        if !self.buckets[0].is_empty() {
+            return Some(self.last);
+        }
+
+        self.buckets
+            .iter()
+            .find(|bucket| !bucket.is_empty())
+            .and_then(|bucket| bucket.iter().map(|(key, _)| *key).min())
+    }
+
+    fn pop(&mut self) -> Option<(K, V)> {
+        if self.buckets[0].is_empty() {
+            self.redistribute();
+        }
+
+        let entry = self.buckets[0].pop()?;
+        self.size -= 1;
+
+        Some(entry)
+    }
+
+    fn push(&mut self, key: K, value: V) {
+        assert!(key >= self.last, "Key is smaller than the last popped key");
+
+        let bucket = self.bucket(key);
+        self.buckets[bucket].push((key, value));
+        self.size += 1;
+    }
+
+    fn size(&self) -> usize {
+        self.size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::MonotoneHeap;
+
+    use super::RadixHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = RadixHeap::<u32, char>::new();
+
+        heap.push(3, 'c');
+        heap.push(1, 'a');
+        heap.push(4, 'd');
+        heap.push(2, 'b');
+
+        assert_eq!(heap.peek_key(), Some(1));
+        assert_eq!(heap.pop(), Some((1, 'a')));
+        assert_eq!(heap.pop(), Some((2, 'b')));
+
+        // Keys equal to the last popped one are still allowed
+        heap.push(2, 'e');
+        assert_eq!(heap.pop(), Some((2, 'e')));
+        assert_eq!(heap.pop(), Some((3, 'c')));
+        assert_eq!(heap.pop(), Some((4, 'd')));
+        assert_eq!(heap.pop(), None);
+        assert_eq!(heap.size(), 0);
+    }
+
+    #[test]
+    fn test_extreme_keys() {
+        let mut heap = RadixHeap::<u8, ()>::new();
+
+        heap.push(u8::MAX, ());
+        heap.push(0, ());
+        heap.push(128, ());
+        heap.push(127, ());
+
+        assert_eq!(heap.pop(), Some((0, ())));
+        assert_eq!(heap.pop(), Some((127, ())));
+        assert_eq!(heap.pop(), Some((128, ())));
+        assert_eq!(heap.pop(), Some((u8::MAX, ())));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Key is smaller than the last popped key")]
+    fn test_non_monotone_push() {
+        let mut heap = RadixHeap::<u64, ()>::new();
+
+        heap.push(10, ());
+        heap.pop();
+        heap.push(9, ());
+    }
+
+    #[test]
+    fn test_random_operations() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut heap = RadixHeap::<u64, usize>::new();
+        let mut sorted: Vec<u64> = Vec::new();
+
+        for step in 0..10_000 {
+            if rng.gen_bool(0.6) {
+                let key = heap.last() + rng.gen_range(0..1_000);
+                heap.push(key, step);
+                let position = sorted.partition_point(|&x| x < key);
+                sorted.insert(position, key);
+            } else {
+                let expected = (!sorted.is_empty()).then(|| sorted.remove(0));
+                assert_eq!(heap.pop().map(|(key, _)| key), expected);
+            }
+
+            assert_eq!(heap.peek_key(), sorted.first().copied());
+            assert_eq!(heap.size(), sorted.len());
+        }
+    }
+}
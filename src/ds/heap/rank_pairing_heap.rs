@@ -0,0 +1,599 @@
+use super::handle::{Handle, Slab};
+use super::invariants::{self, InvariantViolation};
+use super::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
+
+/// Rule giving the rank of a node from the ranks of its children, a missing child having
+/// rank -1
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RankRule {
+    /// One more than the larger rank if both children have the same rank, else the larger
+    #[default]
+    Type1,
+    /// One more than the larger rank if the children's ranks differ by at most one, else
+    /// the larger. Ranks drop less often, so decrease-key repairs stop earlier.
+    Type2,
+}
+
+impl RankRule {
+    fn rank(self, left: isize, right: isize) -> isize {
+        let larger = left.max(right);
+
+        match self {
+            RankRule::Type1 if left == right => larger + 1,
+            RankRule::Type2 if left.abs_diff(right) <= 1 => larger + 1,
+            _ => larger,
+        }
+    }
+}
+
+/// Nodes of half trees in the binary representation: a node comes no later than every
+/// node in its left subtree, while its right subtree is unordered with respect to it.
+///
+/// Roots have no right subtree and use `right` to link the circular root list instead.
+struct Node<T> {
+    element: T,
+    rank: usize,
+    parent: Option<usize>,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T> Node<T> {
+    fn new(element: T) -> Self {
+        Node {
+            element,
+            rank: 0,
+            parent: None,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Rank-pairing heap after Haeupler, Sen and Tarjan, in its one-pass variant.
+///
+/// Like a Fibonacci heap it takes O(1) amortized time per push and decrease-key, and
+/// O(log n) per pop, but a decrease-key cuts a single subtree and then only lowers ranks
+/// along the path above it instead of cascading cuts. Melding takes O(m) for m elements in
+/// the other heap, whose nodes have to be moved into this heap's slab.
+pub struct RankPairingHeap<T> {
+    nodes: Slab<Node<T>>,
+    min: Option<usize>,
+    rule: RankRule,
+}
+
+impl<T: Ord> RankPairingHeap<T> {
+    pub fn new() -> Self {
+        Self::with_rule(RankRule::default())
+    }
+
+    pub fn with_rule(rule: RankRule) -> Self {
+        RankPairingHeap {
+            nodes: Slab::new(),
+            min: None,
+            rule,
+        }
+    }
+
+    pub fn rule(&self) -> RankRule {
+        self.rule
+    }
+
+    fn element(&self, index: usize) -> &T {
+        &self.nodes[index].element
+    }
+
+    fn rank_of(&self, node: Option<usize>) -> isize {
+        node.map_or(-1, |node| self.nodes[node].rank as isize)
+    }
+
+    /// Rank a non-root node should have according to the rank rule
+    fn rule_rank(&self, index: usize) -> usize {
+        let Node { left, right, .. } = self.nodes[index];
+        self.rule.rank(self.rank_of(left), self.rank_of(right)) as usize
+    }
+
+    /// Add a detached half tree to the root list, giving it the rank of a root
+    fn add_root(&mut self, index: usize) {
+        self.nodes[index].parent = None;
+        self.nodes[index].rank = (self.rank_of(self.nodes[index].left) + 1) as usize;
+
+        match self.min {
+            None => {
+                self.nodes[index].right = Some(index);
+                self.min = Some(index);
+            }
+            Some(min) => {
+                self.nodes[index].right = self.nodes[min].right;
+                self.nodes[min].right = Some(index);
+                if self.element(index) < self.element(min) {
+                    self.min = Some(index);
+                }
+            }
+        }
+    }
+
+    /// Link two detached half trees of equal rank, the one with the larger root becoming
+    /// the left child of the other, its former left subtree hanging off the loser's right
+    fn link(&mut self, first: usize, second: usize) -> usize {
+        let (winner, loser) = if self.element(first) <= self.element(second) {
+            (first, second)
+        } else {
+            (second, first)
+        };
+
+        let subtree = self.nodes[winner].left;
+        self.nodes[loser].right = subtree;
+        if let Some(subtree) = subtree {
+            self.nodes[subtree].parent = Some(loser);
+        }
+
+        self.nodes[winner].left = Some(loser);
+        self.nodes[loser].parent = Some(winner);
+        self.nodes[winner].rank += 1;
+        winner
+    }
+
+    /// Detach a non-root node along with its left subtree into a new half tree, its right
+    /// subtree taking its place, then lower the ranks above it
+    fn cut(&mut self, index: usize) {
+        let parent = self.nodes[index].parent.expect("Only non-roots can be cut");
+        let right = self.nodes[index].right.take();
+
+        if self.nodes[parent].left == Some(index) {
+            self.nodes[parent].left = right;
+        } else {
+            self.nodes[parent].right = right;
+        }
+        if let Some(right) = right {
+            self.nodes[right].parent = Some(parent);
+        }
+
+        self.add_root(index);
+        self.restore_ranks(parent);
+    }
+
+    /// Recompute ranks from a node up towards its root until one does not change.
+    ///
+    /// Ranks of children only ever drop, so stopping at the first unchanged rank leaves
+    /// every rank above it consistent.
+    fn restore_ranks(&mut self, mut index: usize) {
+        while let Some(parent) = self.nodes[index].parent {
+            let rank = self.rule_rank(index);
+            if rank >= self.nodes[index].rank {
+                return;
+            }
+
+            self.nodes[index].rank = rank;
+            index = parent;
+        }
+
+        self.nodes[index].rank = (self.rank_of(self.nodes[index].left) + 1) as usize;
+    }
+}
+
+impl<T: Ord> Default for RankPairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Heap<T> for RankPairingHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.min.map(|index| self.element(index))
+    }
+
+    /// The right spine of the minimum's left subtree breaks into new half trees, then a
+    /// single pass over the roots links pairs of equal rank
+    fn pop(&mut self) -> Option<T> {
+        let min = self.min.take()?;
+
+        let mut roots = Vec::new();
+        let mut current = self.nodes[min].right.unwrap();
+        while current != min {
+            roots.push(current);
+            current = self.nodes[current].right.unwrap();
+        }
+
+        let mut child = self.nodes[min].left.take();
+        while let Some(node) = child {
+            child = self.nodes[node].right.take();
+            self.nodes[node].parent = None;
+            self.nodes[node].rank = (self.rank_of(self.nodes[node].left) + 1) as usize;
+            roots.push(node);
+        }
+
+        let mut by_rank: Vec<Option<usize>> = Vec::new();
+        for root in roots {
+            let rank = self.nodes[root].rank;
+            if rank >= by_rank.len() {
+                by_rank.resize(rank + 1, None);
+            }
+
+            match by_rank[rank].take() {
+                Some(other) => {
+                    let linked = self.link(root, other);
+                    self.add_root(linked);
+                }
+                None => by_rank[rank] = Some(root),
+            }
+        }
+        for root in by_rank.into_iter().flatten() {
+            self.add_root(root);
+        }
+
+        let element = self.nodes.remove(min).element;
+        invariants::debug_check(self);
+        Some(element)
+    }
+
+    fn push(&mut self, element: T) {
+        self.insert(element);
+    }
+
+    fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.nodes.values().map(|node| &node.element))
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.min = None;
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        self.min = None;
+        Box::new(self.nodes.drain().into_iter().map(|node| node.element))
+    }
+}
+
+impl<T: Ord> IntoIterator for RankPairingHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the slab
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes
+            .into_values()
+            .map(|node| node.element)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for RankPairingHeap<T> {
+    /// Root lists are spliced in constant time, but the nodes of `other` have to be
+    /// relocated into this heap's slab first, which takes O(m) for m elements in `other`.
+    /// Handles of `self` stay valid, handles of `other` are consumed.
+    fn meld(&mut self, other: Self) {
+        let offset = self.nodes.slots();
+        self.nodes.append(other.nodes, |node| {
+            node.parent = node.parent.map(|index| index + offset);
+            node.left = node.left.map(|index| index + offset);
+            node.right = node.right.map(|index| index + offset);
+        });
+
+        if let Some(other_min) = other.min.map(|index| index + offset) {
+            match self.min {
+                None => self.min = Some(other_min),
+                Some(min) => {
+                    let min_next = self.nodes[min].right;
+                    self.nodes[min].right = self.nodes[other_min].right;
+                    self.nodes[other_min].right = min_next;
+
+                    if self.element(other_min) < self.element(min) {
+                        self.min = Some(other_min);
+                    }
+                }
+            }
+        }
+        invariants::debug_check(self);
+    }
+}
+
+impl<T: Ord> AddressableHeap<T> for RankPairingHeap<T> {
+    fn insert(&mut self, element: T) -> Handle {
+        let handle = self.nodes.insert(Node::new(element));
+        self.add_root(handle.index());
+        invariants::debug_check(self);
+        handle
+    }
+
+    fn get(&self, handle: Handle) -> Option<&T> {
+        self.nodes.resolve(handle).map(|index| self.element(index))
+    }
+
+    fn decrease_key(&mut self, handle: Handle, element: T) {
+        let index = self.nodes.resolve(handle).expect("Invalid heap handle");
+        assert!(
+            element <= *self.element(index),
+            "New key is greater than the current key"
+        );
+        self.nodes[index].element = element;
+
+        // The left subtree stays ordered below the decreased node, only its place under
+        // the parent may be broken
+        if self.nodes[index].parent.is_some() {
+            self.cut(index);
+        } else if self.element(index) < self.element(self.min.unwrap()) {
+            self.min = Some(index);
+        }
+        invariants::debug_check(self);
+    }
+
+    fn delete(&mut self, handle: Handle) -> Option<T> {
+        let index = self.nodes.resolve(handle)?;
+
+        // Cut the node out as if its key was decreased to minus infinity
+        if self.nodes[index].parent.is_some() {
+            self.cut(index);
+        }
+        self.min = Some(index);
+
+        self.pop()
+    }
+}
+
+impl<T: Ord> HeapInvariants for RankPairingHeap<T> {
+    /// Checks half order, parent links and ranks of every node, and that the minimum is
+    /// the smallest root
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        let expected = self.nodes.len();
+        let mut reachable = 0;
+
+        let Some(min) = self.min else {
+            return match expected {
+                0 => Ok(()),
+                _ => Err(InvariantViolation::Size {
+                    reachable,
+                    expected,
+                }),
+            };
+        };
+
+        // Nodes to visit along with the node whose left subtree they are in
+        let mut stack = Vec::new();
+        let mut root = min;
+        loop {
+            reachable += 1;
+            if reachable > expected {
+                return Err(InvariantViolation::Size {
+                    reachable,
+                    expected,
+                });
+            }
+
+            let node = &self.nodes[root];
+            if node.parent.is_some() {
+                return Err(InvariantViolation::ParentLink { node: root });
+            }
+            let rank = (self.rank_of(node.left) + 1) as usize;
+            if node.rank != rank {
+                return Err(InvariantViolation::Rank {
+                    node: root,
+                    stored: node.rank,
+                    expected: rank,
+                });
+            }
+            if node.element < *self.element(min) {
+                return Err(InvariantViolation::HeapOrder {
+                    parent: min,
+                    child: root,
+                });
+            }
+            stack.extend(node.left.map(|left| (left, root)));
+
+            root = node.right.unwrap();
+            if root == min {
+                break;
+            }
+        }
+
+        while let Some((index, ordered_after)) = stack.pop() {
+            reachable += 1;
+            if reachable > expected {
+                return Err(InvariantViolation::Size {
+                    reachable,
+                    expected,
+                });
+            }
+
+            let node = &self.nodes[index];
+            if node.element < *self.element(ordered_after) {
+                return Err(InvariantViolation::HeapOrder {
+                    parent: ordered_after,
+                    child: index,
+                });
+            }
+            let rank = self.rule_rank(index);
+            if node.rank != rank {
+                return Err(InvariantViolation::Rank {
+                    node: index,
+                    stored: node.rank,
+                    expected: rank,
+                });
+            }
+
+            for (child, ordered_after) in [(node.left, index), (node.right, ordered_after)] {
+                if let Some(child) = child {
+                    if self.nodes[child].parent != Some(index) {
+                        return Err(InvariantViolation::ParentLink { node: child });
+                    }
+                    stack.push((child, ordered_after));
+                }
+            }
+        }
+
+        if reachable != expected {
+            return Err(InvariantViolation::Size {
+                reachable,
+                expected,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::{AddressableHeap, Heap, HeapInvariants, MergeableHeap};
+
+    use super::{RankPairingHeap, RankRule};
+
+    const RULES: [RankRule; 2] = [RankRule::Type1, RankRule::Type2];
+
+    #[test]
+    fn test_heap() {
+        for rule in RULES {
+            let mut heap = RankPairingHeap::<i32>::with_rule(rule);
+
+            for i in 1..=100 {
+                heap.push(i);
+            }
+            assert_eq!(heap.peek(), Some(&1));
+
+            for i in -100..=0 {
+                heap.push(i);
+            }
+            assert_eq!(heap.peek(), Some(&-100));
+            assert_eq!(heap.size(), 201);
+
+            for i in -100..=100 {
+                assert_eq!(heap.pop(), Some(i));
+            }
+            assert_eq!(heap.peek(), None);
+            assert_eq!(heap.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        for rule in RULES {
+            let mut heap = RankPairingHeap::<i32>::with_rule(rule);
+            let mut rng = StdRng::seed_from_u64(3);
+
+            // Keys stay distinct modulo 2000, so popped keys identify their handle
+            let mut keys: Vec<i32> = (0..2_000).map(|i| 2_000 * (i + 100) + i).collect();
+            let handles: Vec<_> = keys.iter().map(|&key| heap.insert(key)).collect();
+
+            // Popping builds deep half trees, which decreases then cut apart
+            assert_eq!(heap.pop(), Some(200_000));
+            keys[0] = i32::MAX;
+
+            for _ in 0..5_000 {
+                let index = rng.gen_range(1..keys.len());
+                if keys[index] == i32::MAX {
+                    continue;
+                }
+                keys[index] -= rng.gen_range(0..1_000) * 2_000;
+                heap.decrease_key(handles[index], keys[index]);
+                assert_eq!(heap.get(handles[index]), Some(&keys[index]));
+
+                if rng.gen_bool(0.1) {
+                    let popped = heap.pop().unwrap();
+                    let index = keys.iter().position(|&key| key == popped).unwrap();
+                    keys[index] = i32::MAX;
+                    assert_eq!(heap.get(handles[index]), None);
+                }
+            }
+            assert_eq!(heap.check_invariants(), Ok(()));
+
+            let mut remaining: Vec<_> = keys.into_iter().filter(|&key| key < i32::MAX).collect();
+            remaining.sort();
+            assert_eq!(heap.into_sorted_vec(), remaining);
+        }
+    }
+
+    #[test]
+    fn test_delete() {
+        for rule in RULES {
+            let mut heap = RankPairingHeap::<i32>::with_rule(rule);
+
+            let handles: Vec<_> = (0..20).map(|i| heap.insert(i)).collect();
+            assert_eq!(heap.pop(), Some(0));
+
+            assert_eq!(heap.delete(handles[1]), Some(1));
+            assert_eq!(heap.delete(handles[7]), Some(7));
+            assert_eq!(heap.delete(handles[7]), None);
+            assert_eq!(heap.delete(handles[0]), None);
+            assert_eq!(heap.check_invariants(), Ok(()));
+
+            for i in (2..20).filter(|&i| i != 7) {
+                assert_eq!(heap.pop(), Some(i));
+            }
+            assert_eq!(heap.pop(), None);
+        }
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = RankPairingHeap::<i32>::new();
+        let mut other = RankPairingHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+        other.pop();
+
+        let handle = heap.insert(200);
+        heap.meld(other);
+        heap.decrease_key(handle, -1);
+
+        assert_eq!(heap.size(), 100);
+        assert_eq!(heap.check_invariants(), Ok(()));
+        assert_eq!(heap.pop(), Some(-1));
+        assert_eq!(heap.pop(), Some(0));
+        for i in 2..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld_handles() {
+        for rule in RULES {
+            let mut heap = RankPairingHeap::with_rule(rule);
+            let mut other = RankPairingHeap::with_rule(rule);
+
+            let own: Vec<_> = (10..20).map(|i| heap.insert(i)).collect();
+            let melded: Vec<_> = (20..30).map(|i| other.insert(i)).collect();
+            heap.meld(other);
+
+            // Handles of `other` name the slots of this heap's own nodes after relocation
+            for (i, &handle) in melded.iter().enumerate() {
+                assert_eq!(heap.get(handle), None);
+                assert_eq!(heap.get(own[i]), Some(&(10 + i as i32)));
+            }
+
+            heap.decrease_key(own[9], 0);
+            assert_eq!(heap.check_invariants(), Ok(()));
+            assert_eq!(heap.pop(), Some(0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid heap handle")]
+    fn test_decrease_key_melded_handle() {
+        let mut heap = RankPairingHeap::new();
+        let mut other = RankPairingHeap::new();
+
+        heap.insert(10);
+        let melded = other.insert(20);
+        heap.meld(other);
+
+        heap.decrease_key(melded, 0);
+    }
+}
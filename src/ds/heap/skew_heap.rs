@@ -0,0 +1,261 @@
+use super::handle::Slab;
+use super::{Heap, MergeableHeap};
+
+/// Same layout as a leftist heap node, without the rank
+struct Node<T> {
+    element: T,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+impl<T: Ord> Node<T> {
+    fn new(element: T) -> Node<T> {
+        Node {
+            element,
+            left: None,
+            right: None,
+        }
+    }
+}
+
+/// Self-adjusting leftist heap, which swaps the children of every node on the merge path
+/// instead of keeping ranks.
+///
+/// Right spines can grow linear in the number of elements, so merge walks them in a loop
+/// rather than recursing.
+pub struct SkewHeap<T> {
+    nodes: Slab<Node<T>>,
+    root: Option<usize>,
+}
+
+impl<T: Ord> SkewHeap<T> {
+    pub fn new() -> SkewHeap<T> {
+        SkewHeap {
+            nodes: Slab::new(),
+            root: None,
+        }
+    }
+
+    /// Merge the right spines of two trees top-down, swapping children along the way
+    fn merge_nodes(&mut self, left: Option<usize>, right: Option<usize>) -> Option<usize> {
+        let (mut current, mut other) = match (left, right) {
+            (None, right) => return right,
+            (left, None) => return left,
+            (Some(left_node), Some(right_node)) => {
+                if self.nodes[left_node].element <= self.nodes[right_node].element {
+                    (left_node, right_node)
+                } else {
+                    (right_node, left_node)
+                }
+            }
+        };
+        let root = current;
+
+        loop {
+            // The merged subtree becomes the left child and the old left child moves right
+            let right = self.nodes[current].right;
+            self.nodes[current].right = self.nodes[current].left;
+
+            let Some(right) = right else {
+                self.nodes[current].left = Some(other);
+                break;
+            };
+
+            let next = if self.nodes[right].element <= self.nodes[other].element {
+                right
+            } else {
+                std::mem::replace(&mut other, right)
+            };
+
+            self.nodes[current].left = Some(next);
+            current = next;
+        }
+
+        Some(root)
+    }
+}
+
+impl<T: Ord> Default for SkewHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> Heap<T> for SkewHeap<T> {
+    fn peek(&self) -> Option<&T> {
+        self.root.map(|node| &self.nodes[node].element)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let root = self.root?;
+        let left = self.nodes[root].left;
+        let right = self.nodes[root].right;
+
+        self.root = self.merge_nodes(left, right);
+        Some(self.nodes.remove(root).element)
+    }
+
+    fn push(&mut self, element: T) {
+        let node = self.nodes.insert(Node::new(element)).index();
+        self.root = self.merge_nodes(self.root, Some(node));
+    }
+
+    fn size(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.nodes.values().map(|node| &node.element))
+    }
+
+    fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        self.root = None;
+        Box::new(self.nodes.drain().into_iter().map(|node| node.element))
+    }
+}
+
+impl<T: Ord> IntoIterator for SkewHeap<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order, after being collected out of the slab
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes
+            .into_values()
+            .map(|node| node.element)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<T: Ord> MergeableHeap<T> for SkewHeap<T> {
+    /// Amortized O(log n), on top of moving the nodes of `other` into this heap's slab
+    fn meld(&mut self, other: Self) {
+        let offset = self.nodes.slots();
+        self.nodes.append(other.nodes, |node| {
+            node.left = node.left.map(|index| index + offset);
+            node.right = node.right.map(|index| index + offset);
+        });
+
+        let other_root = other.root.map(|index| index + offset);
+        self.root = self.merge_nodes(self.root, other_root);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ds::heap::{Heap, MergeableHeap};
+
+    use super::SkewHeap;
+
+    #[test]
+    fn test_basic_heap() {
+        let mut heap = SkewHeap::<i32>::new();
+
+        heap.push(1);
+        heap.push(2);
+        heap.push(3);
+        heap.push(4);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), None);
+
+        heap.push(4);
+        heap.push(1);
+        heap.push(3);
+        heap.push(2);
+
+        assert_eq!(heap.pop(), Some(1));
+        assert_eq!(heap.pop(), Some(2));
+        assert_eq!(heap.pop(), Some(3));
+        assert_eq!(heap.pop(), Some(4));
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_heap() {
+        let mut heap: SkewHeap<i32> = SkewHeap::new();
+
+        // Test inserting elements
+        for i in 1..=100 {
+            heap.push(i);
+        }
+
+        // Test the smallest element in the heap
+        assert_eq!(heap.peek(), Some(&1));
+
+        // Test inserting smaller elements after creating the heap
+        for i in -100..=0 {
+            heap.push(i);
+        }
+
+        // Test the smallest element in the heap
+        assert_eq!(heap.peek(), Some(&-100));
+        assert_eq!(heap.size(), 201);
+
+        // Test popping all elements from the heap
+        for i in -100..=100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+
+        // Test heap is empty
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_degenerate_spines() {
+        let mut heap = SkewHeap::<i32>::new();
+        let mut other = SkewHeap::<i32>::new();
+
+        // Interleaved runs leave long paths that a recursive merge would have to descend
+        for i in (0..200_000).step_by(2).rev() {
+            heap.push(i);
+        }
+        for i in (1..200_000).step_by(2) {
+            other.push(i);
+        }
+
+        heap.meld(other);
+
+        assert_eq!(heap.size(), 200_000);
+        for i in 0..200_000 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = SkewHeap::<i32>::new();
+        let mut other = SkewHeap::<i32>::new();
+
+        for i in (0..100).step_by(2) {
+            heap.push(i);
+        }
+        for i in (1..100).step_by(2) {
+            other.push(i);
+        }
+        other.pop();
+
+        heap.meld(other);
+
+        assert_eq!(heap.size(), 99);
+        assert_eq!(heap.pop(), Some(0));
+        for i in 2..100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+}
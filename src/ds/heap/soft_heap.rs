@@ -0,0 +1,321 @@
+struct Node<T> {
+    /// Elements held by the node, all popped under the node's common key
+    items: Vec<T>,
+    /// Key of every element in `items`, no smaller than any of their own keys
+    ckey: T,
+    rank: usize,
+    /// Number of elements the node is refilled up to from its children
+    target: usize,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+impl<T: Ord + Clone> Node<T> {
+    fn leaf(element: T) -> Box<Node<T>> {
+        Box::new(Node {
+            ckey: element.clone(),
+            items: vec![element],
+            rank: 0,
+            target: 1,
+            left: None,
+            right: None,
+        })
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.left.is_none() && self.right.is_none()
+    }
+
+    /// Refill the node from its children until it holds `target` elements or runs out of
+    /// children, taking the elements of the child with the smaller common key each time.
+    ///
+    /// Moving elements up under the child's common key is what corrupts their keys.
+    fn sift(&mut self) {
+        while self.items.len() < self.target && !self.is_leaf() {
+            let swap = match (&self.left, &self.right) {
+                (None, _) => true,
+                (Some(left), Some(right)) => left.ckey > right.ckey,
+                (Some(_), None) => false,
+            };
+            if swap {
+                std::mem::swap(&mut self.left, &mut self.right);
+            }
+
+            let child = self.left.as_mut().unwrap();
+            self.items.append(&mut child.items);
+            self.ckey = child.ckey.clone();
+
+            if child.is_leaf() {
+                self.left = None;
+            } else {
+                child.sift();
+            }
+        }
+    }
+
+    fn iter_nodes(&self) -> impl Iterator<Item = &Node<T>> {
+        let mut stack = vec![self];
+
+        std::iter::from_fn(move || {
+            let node = stack.pop()?;
+            stack.extend(node.left.as_deref());
+            stack.extend(node.right.as_deref());
+            Some(node)
+        })
+    }
+}
+
+/// Soft heap after Kaplan and Zwick's simplification of Chazelle's design.
+///
+/// Elements may have their keys raised, becoming corrupted, in exchange for cheaper
+/// operations: with error rate ε, at most εn of the elements present are corrupted after
+/// n insertions. Popping takes the element with the smallest current key, which a
+/// corrupted element only reaches after its own key.
+///
+/// Trees of the same rank are combined like in a binomial heap, and each node holds a
+/// list of elements sharing a common key. Nodes up to rank ⌈log₂(1/ε)⌉ + 5 hold a single
+/// element, beyond that the number of elements a node gathers grows by half per rank.
+///
+/// The heap does not implement [`Heap`](super::Heap), whose sorting helpers such as
+/// `into_sorted_vec` would silently return unsorted output once elements are corrupted.
+pub struct SoftHeap<T> {
+    /// Tree of rank k at index k, if present
+    trees: Vec<Option<Box<Node<T>>>>,
+    epsilon: f64,
+    /// Highest rank whose nodes hold a single element
+    rank_threshold: usize,
+    size: usize,
+}
+
+impl<T: Ord + Clone> SoftHeap<T> {
+    /// Create a heap with a given error rate, strictly between 0 and 1
+    pub fn new(epsilon: f64) -> Self {
+        assert!(
+            epsilon > 0.0 && epsilon < 1.0,
+            "Error rate must be strictly between 0 and 1"
+        );
+
+        SoftHeap {
+            trees: Vec::new(),
+            epsilon,
+            rank_threshold: (1.0 / epsilon).log2().ceil() as usize + 5,
+            size: 0,
+        }
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// The element `pop` returns next, which is not the smallest one if it is corrupted
+    pub fn peek(&self) -> Option<&T> {
+        self.first_tree()
+            .and_then(|rank| self.trees[rank].as_ref())
+            .and_then(|tree| tree.items.last())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_with_key().map(|(element, _)| element)
+    }
+
+    pub fn push(&mut self, element: T) {
+        self.add_tree(Node::leaf(element));
+        self.size += 1;
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Iterate over the elements in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.nodes().flat_map(|node| node.items.iter())
+    }
+
+    pub fn clear(&mut self) {
+        self.trees.clear();
+        self.size = 0;
+    }
+
+    /// Trees are combined by rank like a binary addition. Both heaps must have been
+    /// created with the same error rate.
+    pub fn meld(&mut self, other: Self) {
+        assert_eq!(
+            self.rank_threshold, other.rank_threshold,
+            "Melded soft heaps must have the same error rate"
+        );
+
+        for tree in other.trees.into_iter().flatten() {
+            self.add_tree(tree);
+        }
+        self.size += other.size;
+    }
+
+    /// Elements whose key has been raised above their own
+    pub fn corrupted(&self) -> impl Iterator<Item = &T> {
+        self.nodes().flat_map(|node| {
+            node.items
+                .iter()
+                .filter(move |&element| *element < node.ckey)
+        })
+    }
+
+    pub fn corrupted_count(&self) -> usize {
+        self.corrupted().count()
+    }
+
+    /// Remove an element with the smallest current key, returning it with that key.
+    ///
+    /// The element is corrupted when it is smaller than the key.
+    pub fn pop_with_key(&mut self) -> Option<(T, T)> {
+        let rank = self.first_tree()?;
+        let root = self.trees[rank].as_mut().unwrap();
+
+        let element = root.items.pop().unwrap();
+        let key = root.ckey.clone();
+
+        if 2 * root.items.len() <= root.target {
+            if !root.is_leaf() {
+                root.sift();
+            } else if root.items.is_empty() {
+                self.trees[rank] = None;
+                while let Some(None) = self.trees.last() {
+                    self.trees.pop();
+                }
+            }
+        }
+
+        self.size -= 1;
+        Some((element, key))
+    }
+
+    fn nodes(&self) -> impl Iterator<Item = &Node<T>> {
+        self.trees
+            .iter()
+            .flatten()
+            .flat_map(|tree| tree.iter_nodes())
+    }
+
+    /// Rank of the tree whose root has the smallest common key
+    fn first_tree(&self) -> Option<usize> {
+        self.trees
+            .iter()
+            .enumerate()
+            .filter_map(|(rank, tree)| tree.as_ref().map(|tree| (rank, &tree.ckey)))
+            .min_by(|(_, x), (_, y)| x.cmp(y))
+            .map(|(rank, _)| rank)
+    }
+
+    /// Make two trees of the same rank the children of a new root, filled from them
+    fn combine(&self, first: Box<Node<T>>, second: Box<Node<T>>) -> Box<Node<T>> {
+        let rank = first.rank + 1;
+        let target = if rank <= self.rank_threshold {
+            1
+        } else {
+            (3 * first.target).div_ceil(2)
+        };
+
+        let mut node = Box::new(Node {
+            items: Vec::new(),
+            // Replaced by the first child pulled up in sift
+            ckey: first.ckey.clone(),
+            rank,
+            target,
+            left: Some(first),
+            right: Some(second),
+        });
+        node.sift();
+        node
+    }
+
+    /// Add a tree, combining it with the tree of equal rank as long as one is present
+    fn add_tree(&mut self, mut tree: Box<Node<T>>) {
+        let mut rank = tree.rank;
+
+        while let Some(existing) = self.trees.get_mut(rank).and_then(Option::take) {
+            tree = self.combine(existing, tree);
+            rank += 1;
+        }
+
+        if rank >= self.trees.len() {
+            self.trees.resize_with(rank + 1, || None);
+        }
+        self.trees[rank] = Some(tree);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use super::SoftHeap;
+
+    #[test]
+    fn test_exact_below_threshold() {
+        // With ε = 0.5 nodes up to rank 6 hold a single element, so 64 elements fit
+        // without any corruption
+        let mut heap = SoftHeap::new(0.5);
+        for i in (0..64).rev() {
+            heap.push(i);
+        }
+
+        assert_eq!(heap.corrupted_count(), 0);
+        assert_eq!(heap.peek(), Some(&0));
+        for i in 0..64 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_corruption_bound() {
+        let mut rng = StdRng::seed_from_u64(11);
+
+        for epsilon in [0.01, 0.1, 0.3] {
+            let mut heap = SoftHeap::new(epsilon);
+            let mut elements: Vec<i32> = (0..20_000).map(|_| rng.gen_range(0..1_000)).collect();
+
+            for &element in &elements {
+                heap.push(element);
+            }
+            assert!(heap.corrupted_count() as f64 <= epsilon * 20_000.0);
+
+            // Keys never decrease between pops, and only ever grow above an element
+            let mut popped = Vec::new();
+            let mut last_key = i32::MIN;
+            while let Some((element, key)) = heap.pop_with_key() {
+                assert!(element <= key);
+                assert!(last_key <= key);
+                last_key = key;
+                popped.push(element);
+
+                if popped.len() % 1_000 == 0 {
+                    assert!(heap.corrupted_count() as f64 <= epsilon * 20_000.0);
+                }
+            }
+
+            popped.sort();
+            elements.sort();
+            assert_eq!(popped, elements);
+        }
+    }
+
+    #[test]
+    fn test_meld() {
+        let mut heap = SoftHeap::new(0.2);
+        let mut other = SoftHeap::new(0.2);
+
+        for i in 0..1_000 {
+            heap.push(2 * i);
+            other.push(2 * i + 1);
+        }
+        heap.meld(other);
+
+        assert_eq!(heap.size(), 2_000);
+        let mut elements: Vec<_> = heap.iter().copied().collect();
+        elements.sort();
+        assert_eq!(elements, (0..2_000).collect::<Vec<_>>());
+        assert!(heap.corrupted().all(|&element| element < 2_000));
+    }
+}
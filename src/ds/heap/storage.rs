@@ -0,0 +1,99 @@
+use super::invariants::InvariantViolation;
+
+/// Storage for the nodes of a pointer-based heap, deciding how a node reaches its children.
+///
+/// Heap algorithms only follow, move and rewrite links, so the same code runs over nodes
+/// that own their children, boxed nodes and nodes kept in a slab and linked by index.
+pub trait NodeStorage<N>: Default {
+    /// What a node holds for each of its children
+    type Link;
+
+    /// Store a detached node and return a link to it
+    fn alloc(&mut self, node: N) -> Self::Link;
+
+    /// Take a node back out of the storage
+    fn free(&mut self, link: Self::Link) -> N;
+
+    fn node<'a>(&'a self, link: &'a Self::Link) -> &'a N;
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut Self::Link) -> &'a mut N;
+
+    /// Move all nodes of another storage into this one, updating the links to its roots
+    fn append(&mut self, other: Self, roots: &mut [Self::Link]);
+
+    /// Record that a node now hangs below `parent`, or became a root if there is none.
+    ///
+    /// Only storages keeping parent links have anything to do.
+    fn set_parent(&mut self, _child: &Self::Link, _parent: Option<&Self::Link>) {}
+
+    /// Check what the storage records about a node reached from `parent` while walking
+    /// down from the roots, `position` numbering the node in the order of the walk
+    fn check_node(
+        &self,
+        _link: &Self::Link,
+        _parent: Option<&Self::Link>,
+        _position: usize,
+    ) -> Result<(), InvariantViolation> {
+        Ok(())
+    }
+
+    /// Check that the `reachable` nodes found walking down from the roots are all the
+    /// stored ones
+    fn check_count(&self, _reachable: usize) -> Result<(), InvariantViolation> {
+        Ok(())
+    }
+}
+
+/// Storage putting every node in a `Box` of its own
+#[derive(Default)]
+pub struct Boxed;
+
+impl<N> NodeStorage<N> for Boxed {
+    type Link = Box<N>;
+
+    fn alloc(&mut self, node: N) -> Box<N> {
+        Box::new(node)
+    }
+
+    fn free(&mut self, link: Box<N>) -> N {
+        *link
+    }
+
+    fn node<'a>(&'a self, link: &'a Box<N>) -> &'a N {
+        link
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut Box<N>) -> &'a mut N {
+        link
+    }
+
+    /// Boxes are owned by their links, so there is nothing to move
+    fn append(&mut self, _other: Self, _roots: &mut [Box<N>]) {}
+}
+
+/// Storage keeping every node inside its parent, such as in a vector of children
+#[derive(Default)]
+pub struct Inline;
+
+impl<N> NodeStorage<N> for Inline {
+    type Link = N;
+
+    fn alloc(&mut self, node: N) -> N {
+        node
+    }
+
+    fn free(&mut self, link: N) -> N {
+        link
+    }
+
+    fn node<'a>(&'a self, link: &'a N) -> &'a N {
+        link
+    }
+
+    fn node_mut<'a>(&'a mut self, link: &'a mut N) -> &'a mut N {
+        link
+    }
+
+    /// Nodes are owned by their links, so there is nothing to move
+    fn append(&mut self, _other: Self, _roots: &mut [N]) {}
+}
@@ -0,0 +1,435 @@
+use super::compare::{Compare, MinOrder};
+use super::invariants::{self, InvariantViolation};
+use super::{Heap, HeapInvariants};
+
+/// Vector of bits packed into words
+#[derive(Default)]
+struct Bits {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bits {
+    fn with_len(len: usize) -> Self {
+        Bits {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn get(&self, index: usize) -> bool {
+        self.words[index / 64] >> (index % 64) & 1 == 1
+    }
+
+    fn set(&mut self, index: usize, value: bool) {
+        let mask = 1 << (index % 64);
+        if value {
+            self.words[index / 64] |= mask;
+        } else {
+            self.words[index / 64] &= !mask;
+        }
+    }
+
+    fn flip(&mut self, index: usize) {
+        self.words[index / 64] ^= 1 << (index % 64);
+    }
+
+    /// Grow or shrink to a given length, new bits being cleared
+    fn resize(&mut self, len: usize) {
+        self.words.resize(len.div_ceil(64), 0);
+        if !len.is_multiple_of(64) {
+            let last = self.words.len() - 1;
+            self.words[last] &= (1 << (len % 64)) - 1;
+        }
+        self.len = len;
+    }
+
+    fn clear(&mut self) {
+        self.words.clear();
+        self.len = 0;
+    }
+}
+
+/// Elements of a weak heap along with their reverse bits, shared by [`WeakHeap`] and
+/// [`weak_heapsort_by`].
+///
+/// Element `i` has its children at `2i + r(i)` and `2i + 1 - r(i)`, flipping the reverse bit
+/// `r(i)` swapping its two subtrees. Every element comes no earlier than its distinguished
+/// ancestor, the parent of the closest ancestor that is a right child. The root has only a
+/// right subtree, which makes it the first element.
+struct WeakOrder<'a, T, C> {
+    data: &'a mut [T],
+    reverse: &'a mut Bits,
+    compare: &'a C,
+}
+
+impl<T, C: Compare<T>> WeakOrder<'_, T, C> {
+    fn is_left_child(&self, index: usize) -> bool {
+        (index & 1 == 1) == self.reverse.get(index / 2)
+    }
+
+    fn distinguished_ancestor(&self, mut index: usize) -> usize {
+        while self.is_left_child(index) {
+            index /= 2;
+        }
+        index / 2
+    }
+
+    /// Put the subtree at `child` under its distinguished ancestor `ancestor` in order,
+    /// with a single comparison. Returns whether they already were in order.
+    fn join(&mut self, ancestor: usize, child: usize) -> bool {
+        if self.compare.lt(&self.data[child], &self.data[ancestor]) {
+            self.data.swap(ancestor, child);
+            self.reverse.flip(child);
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Establish weak heap order over all elements with n - 1 comparisons
+    fn build(&mut self) {
+        for index in (1..self.data.len()).rev() {
+            let ancestor = self.distinguished_ancestor(index);
+            self.join(ancestor, index);
+        }
+    }
+
+    /// Move the last element up towards the root until it meets a distinguished ancestor
+    /// that comes before it
+    fn sift_up(&mut self, mut index: usize) {
+        while index != 0 {
+            let ancestor = self.distinguished_ancestor(index);
+            if self.join(ancestor, index) {
+                break;
+            }
+            index = ancestor;
+        }
+    }
+
+    /// Restore the order after replacing the root, considering only the first `count`
+    /// elements: walk down the left spine of the root's subtree and join the root with
+    /// every element on the way back up, for ⌈log₂ n⌉ comparisons
+    fn sift_down_within(&mut self, count: usize) {
+        if count < 2 {
+            return;
+        }
+
+        let mut index = 1;
+        loop {
+            let left = 2 * index + self.reverse.get(index) as usize;
+            if left >= count {
+                break;
+            }
+            index = left;
+        }
+
+        while index > 0 {
+            self.join(0, index);
+            index /= 2;
+        }
+    }
+}
+
+/// Weak heap stored in a vector with a bitvector of reverse bits, ordered by a comparator.
+///
+/// A weak heap relaxes the order of a binary heap to hold only between an element and its
+/// distinguished ancestor. This lets it build in n - 1 comparisons and pop in at most
+/// ⌈log₂ n⌉, close to the minimum any comparison-based heap needs.
+pub struct WeakHeap<T, C = MinOrder> {
+    data: Vec<T>,
+    reverse: Bits,
+    compare: C,
+}
+
+impl<T: Ord> WeakHeap<T> {
+    pub fn new() -> Self {
+        Self::with_comparator(MinOrder)
+    }
+
+    /// Build a heap from a vector with n - 1 comparisons
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        Self::from_vec_with_comparator(vec, MinOrder)
+    }
+}
+
+impl<T, C: Compare<T>> WeakHeap<T, C> {
+    pub fn with_comparator(compare: C) -> Self {
+        WeakHeap {
+            data: Vec::new(),
+            reverse: Bits::default(),
+            compare,
+        }
+    }
+
+    pub fn from_vec_with_comparator(vec: Vec<T>, compare: C) -> Self {
+        let mut heap = WeakHeap {
+            reverse: Bits::with_len(vec.len()),
+            data: vec,
+            compare,
+        };
+        heap.order().build();
+        invariants::debug_check(&heap);
+        heap
+    }
+
+    fn order(&mut self) -> WeakOrder<'_, T, C> {
+        WeakOrder {
+            data: &mut self.data,
+            reverse: &mut self.reverse,
+            compare: &self.compare,
+        }
+    }
+}
+
+impl<T, C: Compare<T> + Default> Default for WeakHeap<T, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Heap<T> for WeakHeap<T, C> {
+    fn push(&mut self, element: T) {
+        let index = self.data.len();
+        self.data.push(element);
+        self.reverse.resize(index + 1);
+
+        // A new element at an even index becomes the left child of its parent, which must
+        // not have a right subtree hanging there
+        if index.is_multiple_of(2) && index > 0 {
+            self.reverse.set(index / 2, false);
+        }
+
+        self.order().sift_up(index);
+        invariants::debug_check(self);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        let last = self.data.pop()?;
+        self.reverse.resize(self.data.len());
+
+        if self.data.is_empty() {
+            return Some(last);
+        }
+
+        let first = std::mem::replace(&mut self.data[0], last);
+        let count = self.data.len();
+        self.order().sift_down_within(count);
+        invariants::debug_check(self);
+        Some(first)
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        Box::new(self.data.iter())
+    }
+
+    fn clear(&mut self) {
+        self.data.clear();
+        self.reverse.clear();
+    }
+
+    fn drain<'a>(&'a mut self) -> Box<dyn Iterator<Item = T> + 'a>
+    where
+        T: 'a,
+    {
+        self.reverse.clear();
+        Box::new(self.data.drain(..))
+    }
+
+    /// Weak-heapsort in place: the first element is repeatedly swapped behind a shrinking
+    /// heap prefix, which leaves the vector in reverse heap order
+    fn into_sorted_vec(mut self) -> Vec<T> {
+        for end in (1..self.data.len()).rev() {
+            self.data.swap(0, end);
+            self.order().sift_down_within(end);
+        }
+
+        self.data.reverse();
+        self.data
+    }
+}
+
+impl<T, C: Compare<T>> IntoIterator for WeakHeap<T, C> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    /// Elements come out in arbitrary order
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+impl<T, C: Compare<T> + Default> FromIterator<T> for WeakHeap<T, C> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec_with_comparator(iter.into_iter().collect(), C::default())
+    }
+}
+
+impl<T, C: Compare<T>> Extend<T> for WeakHeap<T, C> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for element in iter {
+            self.push(element);
+        }
+    }
+}
+
+impl<T, C: Compare<T>> HeapInvariants for WeakHeap<T, C> {
+    /// Checks that no element comes before its distinguished ancestor
+    fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        if self.reverse.len != self.data.len() {
+            return Err(InvariantViolation::Size {
+                reachable: self.reverse.len,
+                expected: self.data.len(),
+            });
+        }
+
+        let is_left_child = |index: usize| (index & 1 == 1) == self.reverse.get(index / 2);
+        for child in 1..self.data.len() {
+            let mut index = child;
+            while is_left_child(index) {
+                index /= 2;
+            }
+            let parent = index / 2;
+
+            if self.compare.lt(&self.data[child], &self.data[parent]) {
+                return Err(InvariantViolation::HeapOrder { parent, child });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Sort a slice in ascending order with weak-heapsort, in at most n⌈log₂ n⌉ comparisons
+pub fn weak_heapsort<T: Ord>(slice: &mut [T]) {
+    weak_heapsort_by(slice, MinOrder);
+}
+
+/// Sort a slice with weak-heapsort, elements coming first in the comparator's order first
+pub fn weak_heapsort_by<T, C: Compare<T>>(slice: &mut [T], compare: C) {
+    // The heap keeps the last element in sorted order at its root
+    let reversed = |a: &T, b: &T| compare.compare(b, a);
+    let mut reverse = Bits::with_len(slice.len());
+    let mut order = WeakOrder {
+        data: slice,
+        reverse: &mut reverse,
+        compare: &reversed,
+    };
+
+    order.build();
+    for end in (1..order.data.len()).rev() {
+        order.data.swap(0, end);
+        order.sift_down_within(end);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    use crate::ds::heap::compare::MaxOrder;
+    use crate::ds::heap::invariants::InvariantViolation;
+    use crate::ds::heap::{Heap, HeapInvariants};
+
+    use super::{weak_heapsort, weak_heapsort_by, WeakHeap};
+
+    #[test]
+    fn test_heap() {
+        let mut heap = WeakHeap::<i32>::new();
+
+        for i in 1..=100 {
+            heap.push(i);
+        }
+        for i in -100..=0 {
+            heap.push(i);
+        }
+        assert_eq!(heap.peek(), Some(&-100));
+        assert_eq!(heap.check_invariants(), Ok(()));
+
+        for i in -100..=100 {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.peek(), None);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut heap = WeakHeap::from_vec_with_comparator((0..100).collect(), MaxOrder);
+        heap.extend([42, 150]);
+        assert_eq!(heap.check_invariants(), Ok(()));
+
+        assert_eq!(heap.pop(), Some(150));
+        for i in (42..100).rev() {
+            assert_eq!(heap.pop(), Some(i));
+        }
+        assert_eq!(heap.pop(), Some(42));
+
+        let heap: WeakHeap<i32> = (0..100).rev().collect();
+        assert_eq!(heap.into_sorted_vec(), (0..100).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_invariants() {
+        let mut heap = WeakHeap::from_vec((0..50).collect());
+        assert_eq!(heap.check_invariants(), Ok(()));
+
+        // A sorted vector builds without any swaps, leaving every reverse bit clear and
+        // element 3 as the right child of element 1
+        heap.data.swap(1, 3);
+        assert_eq!(
+            heap.check_invariants(),
+            Err(InvariantViolation::HeapOrder {
+                parent: 1,
+                child: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_weak_heapsort() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for n in [0, 1, 2, 3, 10, 1_000] {
+            let mut vec: Vec<i32> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+            let mut expected = vec.clone();
+            expected.sort();
+
+            weak_heapsort(&mut vec);
+            assert_eq!(vec, expected);
+
+            expected.reverse();
+            weak_heapsort_by(&mut vec, MaxOrder);
+            assert_eq!(vec, expected);
+        }
+    }
+
+    #[test]
+    fn test_comparison_count() {
+        let comparisons = Cell::new(0);
+        let counting = |a: &i32, b: &i32| {
+            comparisons.set(comparisons.get() + 1);
+            a.cmp(b)
+        };
+
+        let n = 1 << 12;
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut vec: Vec<i32> = (0..n).map(|_| rng.gen()).collect();
+
+        // Building takes n - 1 comparisons, every sift down at most log₂ n more
+        weak_heapsort_by(&mut vec, counting);
+        assert!(vec.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert!(comparisons.get() <= n - 1 + (n - 1) * 12);
+    }
+}
@@ -24,6 +24,9 @@ fn main() {
 
     // BEGIN REGISTRATION BLOCK
     analysis::heaps::register_commands(&mut cm);
+    analysis::double_ended_heaps::register_commands(&mut cm);
+    analysis::concurrent_heaps::register_commands(&mut cm);
+    analysis::external_heaps::register_commands(&mut cm);
     // END REGISTRATION BLOCK
 
     let mut command = Command::new("algods")